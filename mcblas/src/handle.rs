@@ -0,0 +1,286 @@
+use crate::{
+    bindings::{mcblasHandle_t, mcblasOperation_t},
+    matrix::to_int,
+    BlasError, Layout, Mat, MatMut, Scalar,
+};
+use context_spore::{impl_spore, AsRaw};
+use runtime::{CurrentCtx, DevByte, Stream};
+use std::{
+    ffi::{c_longlong, c_void},
+    marker::PhantomData,
+    ptr::null_mut,
+};
+
+impl_spore!(Blas and BlasSpore by (CurrentCtx, mcblasHandle_t));
+
+impl<'ctx> Blas<'ctx> {
+    #[inline]
+    pub fn new(ctx: &'ctx CurrentCtx) -> Self {
+        let mut handle = null_mut();
+        mcblas!(mcblasCreate(&mut handle));
+        Self(unsafe { ctx.wrap_raw(handle) }, PhantomData)
+    }
+}
+
+impl Drop for Blas<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        mcblas!(mcblasDestroy(self.0.rss));
+    }
+}
+
+impl AsRaw for Blas<'_> {
+    type Raw = mcblasHandle_t;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0.rss
+    }
+}
+
+/// 一次 GEMM 的参数，已按 `c = alpha * op(a) * op(b) + beta * c` 检查过形状。
+struct Gemm {
+    trans_a: mcblasOperation_t,
+    trans_b: mcblasOperation_t,
+    m: i32,
+    n: i32,
+    k: i32,
+    lda: i32,
+    ldb: i32,
+    ldc: i32,
+}
+
+impl Gemm {
+    fn new(a: &Layout, b: &Layout, c: &Layout) -> Result<Self, BlasError> {
+        let (m, k) = a.shape();
+        let (k_, n) = b.shape();
+        if k != k_ || c.shape() != (m, n) {
+            return Err(BlasError::ShapeMismatch);
+        }
+        let op = |trans| {
+            if trans {
+                mcblasOperation_t::MCBLAS_OP_T
+            } else {
+                mcblasOperation_t::MCBLAS_OP_N
+            }
+        };
+        Ok(Self {
+            trans_a: op(a.trans),
+            trans_b: op(b.trans),
+            m: to_int(m)?,
+            n: to_int(n)?,
+            k: to_int(k)?,
+            lda: a.ld_raw()?,
+            ldb: b.ld_raw()?,
+            ldc: c.ld_raw()?,
+        })
+    }
+}
+
+impl Blas<'_> {
+    /// `c = alpha * op(a) * op(b) + beta * c`。
+    ///
+    /// 若 `c` 包含多个矩阵则执行跨步批量 GEMM，此时 `a` 和 `b` 的批数须与 `c` 相同或为 1，
+    /// 批数为 1 的操作数被广播到每个批次。
+    pub fn gemm<T: Scalar>(
+        &self,
+        stream: &Stream,
+        alpha: f32,
+        a: &Mat<T>,
+        b: &Mat<T>,
+        beta: f32,
+        c: &mut MatMut<T>,
+    ) -> Result<(), BlasError> {
+        let (la, lb, lc) = (*a.layout(), *b.layout(), *c.layout());
+        let g = Gemm::new(&la, &lb, &lc)?;
+        let batch = lc.batch;
+        let stride = |l: &Layout| -> Result<c_longlong, BlasError> {
+            match l.batch {
+                1 => Ok(0),
+                n if n == batch => c_longlong::try_from(l.stride).map_err(|_| BlasError::TooLarge),
+                _ => Err(BlasError::BatchMismatch),
+            }
+        };
+        let (sa, sb) = (stride(&la)?, stride(&lb)?);
+        self.bind(stream);
+        if batch == 1 {
+            mcblas!(mcblasGemmEx(
+                self.0.rss,
+                g.trans_a,
+                g.trans_b,
+                g.m,
+                g.n,
+                g.k,
+                (&alpha as *const f32).cast(),
+                a.as_ptr().cast(),
+                T::DATA_TYPE,
+                g.lda,
+                b.as_ptr().cast(),
+                T::DATA_TYPE,
+                g.ldb,
+                (&beta as *const f32).cast(),
+                c.as_mut_ptr().cast(),
+                T::DATA_TYPE,
+                g.ldc,
+                mcblasComputeType_t::MCBLAS_COMPUTE_32F,
+                mcblasGemmAlgo_t::MCBLAS_GEMM_DEFAULT,
+            ));
+        } else {
+            let sc = c_longlong::try_from(lc.stride).map_err(|_| BlasError::TooLarge)?;
+            mcblas!(mcblasGemmStridedBatchedEx(
+                self.0.rss,
+                g.trans_a,
+                g.trans_b,
+                g.m,
+                g.n,
+                g.k,
+                (&alpha as *const f32).cast(),
+                a.as_ptr().cast(),
+                T::DATA_TYPE,
+                g.lda,
+                sa,
+                b.as_ptr().cast(),
+                T::DATA_TYPE,
+                g.ldb,
+                sb,
+                (&beta as *const f32).cast(),
+                c.as_mut_ptr().cast(),
+                T::DATA_TYPE,
+                g.ldc,
+                sc,
+                to_int(batch)?,
+                mcblasComputeType_t::MCBLAS_COMPUTE_32F,
+                mcblasGemmAlgo_t::MCBLAS_GEMM_DEFAULT,
+            ));
+        }
+        Ok(())
+    }
+
+    /// 对任意位置的一组矩阵执行 GEMM，所有批次的布局必须相同。
+    ///
+    /// 指针数组经 `stream` 拷贝到设备，在计算完成后释放。
+    pub fn gemm_batched<T: Scalar>(
+        &self,
+        stream: &Stream,
+        alpha: f32,
+        a: &[Mat<T>],
+        b: &[Mat<T>],
+        beta: f32,
+        c: &mut [MatMut<T>],
+    ) -> Result<(), BlasError> {
+        let (Some(a0), Some(b0), Some(c0)) = (a.first(), b.first(), c.first()) else {
+            return if a.len() == b.len() && b.len() == c.len() {
+                Ok(())
+            } else {
+                Err(BlasError::BatchMismatch)
+            };
+        };
+        if a.len() != c.len() || b.len() != c.len() {
+            return Err(BlasError::BatchMismatch);
+        }
+        let (la, lb, lc) = (*a0.layout(), *b0.layout(), *c0.layout());
+        if a.iter().any(|m| *m.layout() != la)
+            || b.iter().any(|m| *m.layout() != lb)
+            || c.iter().any(|m| *m.layout() != lc)
+            || [la.batch, lb.batch, lc.batch] != [1; 3]
+        {
+            return Err(BlasError::ShapeMismatch);
+        }
+        let g = Gemm::new(&la, &lb, &lc)?;
+        let batch = to_int(c.len())?;
+
+        let ptrs = a
+            .iter()
+            .map(|m| m.as_ptr() as *mut c_void)
+            .chain(b.iter().map(|m| m.as_ptr() as *mut c_void))
+            .chain(c.iter_mut().map(|m| m.as_mut_ptr().cast()))
+            .collect::<Vec<_>>();
+        let mut dev = stream.ctx().malloc::<*mut c_void>(ptrs.len());
+        stream.memcpy_h2d(&mut dev, &ptrs);
+        let ptrs = dev.as_ptr().cast::<*mut c_void>();
+        let n = c.len();
+
+        self.bind(stream);
+        mcblas!(mcblasGemmBatchedEx(
+            self.0.rss,
+            g.trans_a,
+            g.trans_b,
+            g.m,
+            g.n,
+            g.k,
+            (&alpha as *const f32).cast(),
+            ptrs.cast(),
+            T::DATA_TYPE,
+            g.lda,
+            ptrs.add(n).cast(),
+            T::DATA_TYPE,
+            g.ldb,
+            (&beta as *const f32).cast(),
+            ptrs.add(2 * n).cast(),
+            T::DATA_TYPE,
+            g.ldc,
+            batch,
+            mcblasComputeType_t::MCBLAS_COMPUTE_32F,
+            mcblasGemmAlgo_t::MCBLAS_GEMM_DEFAULT,
+        ));
+        dev.drop_on(stream);
+        Ok(())
+    }
+
+    /// `y = alpha * op(a) * x + beta * y`，`x` 和 `y` 是连续存储的向量。
+    pub fn gemv<T: Scalar>(
+        &self,
+        stream: &Stream,
+        alpha: f32,
+        a: &Mat<T>,
+        x: &[DevByte],
+        beta: f32,
+        y: &mut [DevByte],
+    ) -> Result<(), BlasError> {
+        let (m, k) = a.layout().shape();
+        let x = Mat::<T>::new(x, k, 1)?;
+        let mut y = MatMut::<T>::new(y, m, 1)?;
+        self.gemm(stream, alpha, a, &x, beta, &mut y)
+    }
+
+    #[inline]
+    fn bind(&self, stream: &Stream) {
+        mcblas!(mcblasSetStream(self.0.rss, stream.as_raw() as _));
+    }
+}
+
+#[test]
+fn test_gemm() {
+    use half::f16;
+    use runtime::{memcpy_d2h, memcpy_h2d, Device, NoDevice};
+
+    if let Err(NoDevice) = runtime::init() {
+        return;
+    }
+    Device::new(0).context().apply(|ctx| {
+        let stream = ctx.stream();
+        let blas = Blas::new(ctx);
+
+        // a: 2x3, b: 3x2，列主序
+        let a = [1., 2., 3., 4., 5., 6.].map(f16::from_f32);
+        let b = [1., 0., 1., 0., 1., 0.].map(f16::from_f32);
+        let mut da = ctx.malloc::<f16>(a.len());
+        let mut db = ctx.malloc::<f16>(b.len());
+        let mut dc = ctx.malloc::<f16>(4);
+        memcpy_h2d(&mut da, &a);
+        memcpy_h2d(&mut db, &b);
+
+        let ma = Mat::<f16>::new(&da, 2, 3).unwrap();
+        let mb = Mat::<f16>::new(&db, 3, 2).unwrap();
+        let mut mc = MatMut::<f16>::new(&mut dc, 2, 2).unwrap();
+        assert_eq!(
+            blas.gemm(&stream, 1., &ma, &ma, 0., &mut mc).err(),
+            Some(BlasError::ShapeMismatch)
+        );
+        blas.gemm(&stream, 1., &ma, &mb, 0., &mut mc).unwrap();
+        stream.synchronize();
+
+        let mut c = [f16::ZERO; 4];
+        memcpy_d2h(&mut c, &dc);
+        assert_eq!(c.map(f16::to_f32), [6., 8., 3., 4.]);
+    });
+}
@@ -0,0 +1,67 @@
+#![cfg(detected_mx)]
+
+#[macro_use]
+#[allow(
+    unused,
+    non_upper_case_globals,
+    non_camel_case_types,
+    non_snake_case,
+    clippy::useless_transmute,
+    clippy::too_many_arguments
+)]
+pub mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+    #[macro_export]
+    macro_rules! mcblas {
+        ($f:expr) => {{
+            #[allow(unused_imports)]
+            use $crate::bindings::*;
+            #[allow(unused_unsafe, clippy::macro_metavars_in_unsafe)]
+            let status = unsafe { $f };
+            assert_eq!(status, mcblasStatus_t::MCBLAS_STATUS_SUCCESS);
+        }};
+    }
+}
+
+mod handle;
+mod matrix;
+
+pub use handle::{Blas, BlasSpore};
+pub use matrix::{Layout, Mat, MatMut};
+
+use bindings::macaDataType_t;
+use half::{bf16, f16};
+
+/// 可参与 BLAS 计算的元素类型，累加总是以 `f32` 进行。
+pub trait Scalar: Copy + 'static {
+    const DATA_TYPE: macaDataType_t;
+}
+
+impl Scalar for f32 {
+    const DATA_TYPE: macaDataType_t = macaDataType_t::MACA_R_32F;
+}
+
+impl Scalar for f16 {
+    const DATA_TYPE: macaDataType_t = macaDataType_t::MACA_R_16F;
+}
+
+impl Scalar for bf16 {
+    const DATA_TYPE: macaDataType_t = macaDataType_t::MACA_R_16BF;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BlasError {
+    /// 主维小于行数。
+    LeadingDimension { ld: usize, rows: usize },
+    /// 批间跨度小于一个矩阵的存储，输出矩阵会互相覆盖。
+    Stride { stride: usize, required: usize },
+    /// 布局要求的存储超过了切片长度，以字节计。
+    OutOfBounds { required: usize, len: usize },
+    /// 参与运算的矩阵形状不匹配。
+    ShapeMismatch,
+    /// 参与运算的矩阵批数不匹配。
+    BatchMismatch,
+    /// 尺寸超过了库接口能够表示的范围。
+    TooLarge,
+}
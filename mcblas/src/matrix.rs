@@ -0,0 +1,204 @@
+use crate::{BlasError, Scalar};
+use runtime::DevByte;
+use std::{ffi::c_int, marker::PhantomData, mem::size_of};
+
+/// 列主序矩阵在存储中的布局，可以包含多个等跨度排列的矩阵。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Layout {
+    pub rows: usize,
+    pub cols: usize,
+    /// 主维，即相邻两列首元素的距离，以元素计。
+    pub ld: usize,
+    /// 批中相邻两个矩阵首元素的距离，以元素计。
+    pub stride: usize,
+    pub batch: usize,
+    /// 参与运算时是否转置。
+    pub trans: bool,
+}
+
+impl Layout {
+    #[inline]
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            ld: rows,
+            stride: rows.saturating_mul(cols),
+            batch: 1,
+            trans: false,
+        }
+    }
+
+    /// 参与运算时的形状，考虑转置。
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) {
+        if self.trans {
+            (self.cols, self.rows)
+        } else {
+            (self.rows, self.cols)
+        }
+    }
+
+    /// 布局覆盖的元素数，超出 `usize` 范围时返回 [`BlasError::TooLarge`]。
+    pub fn required(&self) -> Result<usize, BlasError> {
+        if self.rows == 0 || self.cols == 0 || self.batch == 0 {
+            return Ok(0);
+        }
+        self.stride
+            .checked_mul(self.batch - 1)
+            .and_then(|n| n.checked_add(self.ld.checked_mul(self.cols - 1)?))
+            .and_then(|n| n.checked_add(self.rows))
+            .ok_or(BlasError::TooLarge)
+    }
+
+    fn check<T: Scalar>(&self, len: usize) -> Result<(), BlasError> {
+        if self.ld < self.rows.max(1) {
+            return Err(BlasError::LeadingDimension {
+                ld: self.ld,
+                rows: self.rows,
+            });
+        }
+        let required = self
+            .required()?
+            .checked_mul(size_of::<T>())
+            .ok_or(BlasError::TooLarge)?;
+        if required > len {
+            return Err(BlasError::OutOfBounds { required, len });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn ld_raw(&self) -> Result<c_int, BlasError> {
+        to_int(self.ld)
+    }
+}
+
+/// 只读的类型化设备矩阵。
+#[derive(Clone, Copy)]
+pub struct Mat<'a, T> {
+    data: &'a [DevByte],
+    layout: Layout,
+    _t: PhantomData<T>,
+}
+
+/// 可写的类型化设备矩阵。
+pub struct MatMut<'a, T> {
+    data: &'a mut [DevByte],
+    layout: Layout,
+    _t: PhantomData<T>,
+}
+
+impl<'a, T: Scalar> Mat<'a, T> {
+    /// 连续存储的 `rows`x`cols` 矩阵。
+    #[inline]
+    pub fn new(data: &'a [DevByte], rows: usize, cols: usize) -> Result<Self, BlasError> {
+        Self::with_layout(data, Layout::new(rows, cols))
+    }
+
+    pub fn with_layout(data: &'a [DevByte], layout: Layout) -> Result<Self, BlasError> {
+        layout.check::<T>(data.len())?;
+        Ok(Self {
+            data,
+            layout,
+            _t: PhantomData,
+        })
+    }
+
+    /// 转置参与运算。
+    #[inline]
+    pub fn t(mut self) -> Self {
+        self.layout.trans = !self.layout.trans;
+        self
+    }
+
+    #[inline]
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *const DevByte {
+        self.data.as_ptr()
+    }
+}
+
+impl<'a, T: Scalar> MatMut<'a, T> {
+    #[inline]
+    pub fn new(data: &'a mut [DevByte], rows: usize, cols: usize) -> Result<Self, BlasError> {
+        Self::with_layout(data, Layout::new(rows, cols))
+    }
+
+    /// 输出矩阵不能转置，多个矩阵之间不能重叠。
+    pub fn with_layout(data: &'a mut [DevByte], layout: Layout) -> Result<Self, BlasError> {
+        if layout.trans {
+            return Err(BlasError::ShapeMismatch);
+        }
+        let required = layout
+            .ld
+            .checked_mul(layout.cols)
+            .ok_or(BlasError::TooLarge)?;
+        if layout.batch > 1 && layout.stride < required {
+            return Err(BlasError::Stride {
+                stride: layout.stride,
+                required,
+            });
+        }
+        layout.check::<T>(data.len())?;
+        Ok(Self {
+            data,
+            layout,
+            _t: PhantomData,
+        })
+    }
+
+    #[inline]
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    #[inline]
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut DevByte {
+        self.data.as_mut_ptr()
+    }
+}
+
+#[inline]
+pub(crate) fn to_int(n: usize) -> Result<c_int, BlasError> {
+    c_int::try_from(n).map_err(|_| BlasError::TooLarge)
+}
+
+#[test]
+fn test_layout() {
+    let layout = Layout {
+        ld: 8,
+        stride: 64,
+        batch: 3,
+        ..Layout::new(6, 5)
+    };
+    assert_eq!(layout.required(), Ok(64 * 2 + 8 * 4 + 6));
+    assert_eq!(layout.shape(), (6, 5));
+    assert_eq!(
+        Layout { ld: 4, ..layout }.check::<f32>(usize::MAX),
+        Err(BlasError::LeadingDimension { ld: 4, rows: 6 })
+    );
+    assert_eq!(
+        layout.check::<f32>(100),
+        Err(BlasError::OutOfBounds {
+            required: 166 * 4,
+            len: 100,
+        })
+    );
+    assert_eq!(Layout::new(0, 5).required(), Ok(0));
+    assert_eq!(
+        Layout {
+            stride: usize::MAX,
+            ..layout
+        }
+        .required(),
+        Err(BlasError::TooLarge)
+    );
+    assert_eq!(
+        Layout::new(usize::MAX / 2, 1).check::<f32>(usize::MAX),
+        Err(BlasError::TooLarge)
+    );
+}
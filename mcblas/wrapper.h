@@ -0,0 +1 @@
+#include <mcblas/mcblas.h>
@@ -0,0 +1,25 @@
+use std::{env, path::PathBuf};
+
+fn main() {
+    use build_script_cfg::Cfg;
+    use search_mx_tools::{MxLibrary, MxToolkit};
+
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let mx_cfg = Cfg::new("detected_mx");
+    MxToolkit::emit_rerun_if_env_changed();
+    let Some(mx) = MxToolkit::find().filter(|mx| mx.has_library(MxLibrary::Ccl)) else {
+        return;
+    };
+    mx_cfg.define();
+    mx.link(MxLibrary::Ccl);
+
+    let bindings = mx
+        .bindgen("wrapper.h", "mccl.*", "mcclResult_t")
+        .generate()
+        .expect("Unable to generate bindings");
+    let out_path = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    bindings
+        .write_to_file(out_path.join("bindings.rs"))
+        .expect("Couldn't write bindings!");
+}
@@ -0,0 +1,214 @@
+use crate::{bindings::mcclComm_t, CclType, ReduceOp, UniqueId};
+use context_spore::AsRaw;
+use runtime::{CurrentCtx, DevByte, Device, Stream};
+use std::{ffi::c_int, mem::size_of, ptr::null_mut};
+
+/// 集合通信组中的一个参与者，对应一张卡。
+///
+/// 集合通信须在这张卡的流上发起，否则 panic。
+pub struct Communicator {
+    comm: mcclComm_t,
+    dev: c_int,
+}
+
+unsafe impl Send for Communicator {}
+
+impl Communicator {
+    /// 以 `rank` 的身份加入 `id` 标识的通信组，所有参与者都调用后才会返回。
+    ///
+    /// 通信组绑定到调用线程当前上下文所在的设备，没有当前上下文时 panic。
+    pub fn new(id: &UniqueId, rank: usize, nranks: usize) -> Self {
+        assert!(rank < nranks);
+        let dev = CurrentCtx::apply_current(|ctx| unsafe { ctx.dev().as_raw() })
+            .expect("Communicator::new needs a current context");
+        let mut comm = null_mut();
+        mccl!(mcclCommInitRank(
+            &mut comm,
+            nranks as _,
+            id.to_raw(),
+            rank as _
+        ));
+        Self { comm, dev }
+    }
+
+    /// 在单个进程中为 `devices` 创建通信组，按设备顺序排列。
+    pub fn init_all(devices: &[Device]) -> Vec<Self> {
+        let list = devices
+            .iter()
+            .map(|dev| unsafe { dev.as_raw() })
+            .collect::<Vec<c_int>>();
+        let mut comms = vec![null_mut(); list.len()];
+        mccl!(mcclCommInitAll(
+            comms.as_mut_ptr(),
+            list.len() as _,
+            list.as_ptr()
+        ));
+        comms
+            .into_iter()
+            .zip(list)
+            .map(|(comm, dev)| Self { comm, dev })
+            .collect()
+    }
+
+    #[inline]
+    pub fn count(&self) -> usize {
+        let mut count = 0;
+        mccl!(mcclCommCount(self.comm, &mut count));
+        count as _
+    }
+
+    #[inline]
+    pub fn rank(&self) -> usize {
+        let mut rank = 0;
+        mccl!(mcclCommUserRank(self.comm, &mut rank));
+        rank as _
+    }
+
+    /// 对所有参与者的 `src` 归约，结果写入每个参与者的 `dst`。`src` 为 `None` 时原地归约。
+    pub fn all_reduce<T: CclType>(
+        &self,
+        dst: &mut [DevByte],
+        src: Option<&[DevByte]>,
+        op: ReduceOp,
+        stream: &Stream,
+    ) {
+        self.check_stream(stream);
+        let src = src.map_or(dst.as_ptr(), |src| {
+            assert_eq!(src.len(), dst.len());
+            src.as_ptr()
+        });
+        mccl!(mcclAllReduce(
+            src.cast(),
+            dst.as_mut_ptr().cast(),
+            count::<T>(dst),
+            T::DATA_TYPE,
+            op.into(),
+            self.comm,
+            stream.as_raw() as _
+        ));
+    }
+
+    /// 按序号顺序拼接所有参与者的 `src`，写入每个参与者的 `dst`。
+    pub fn all_gather<T: CclType>(&self, dst: &mut [DevByte], src: &[DevByte], stream: &Stream) {
+        self.check_stream(stream);
+        assert_eq!(dst.len(), src.len() * self.count());
+        mccl!(mcclAllGather(
+            src.as_ptr().cast(),
+            dst.as_mut_ptr().cast(),
+            count::<T>(src),
+            T::DATA_TYPE,
+            self.comm,
+            stream.as_raw() as _
+        ));
+    }
+
+    /// 归约所有参与者的 `src`，结果按序号切分，每个参与者得到其中一段。
+    pub fn reduce_scatter<T: CclType>(
+        &self,
+        dst: &mut [DevByte],
+        src: &[DevByte],
+        op: ReduceOp,
+        stream: &Stream,
+    ) {
+        self.check_stream(stream);
+        assert_eq!(src.len(), dst.len() * self.count());
+        mccl!(mcclReduceScatter(
+            src.as_ptr().cast(),
+            dst.as_mut_ptr().cast(),
+            count::<T>(dst),
+            T::DATA_TYPE,
+            op.into(),
+            self.comm,
+            stream.as_raw() as _
+        ));
+    }
+
+    /// 将 `root` 的数据复制到所有参与者的 `dst`。
+    ///
+    /// `src` 只在 `root` 上使用，为 `None` 时从 `root` 的 `dst` 原地广播。
+    pub fn broadcast<T: CclType>(
+        &self,
+        dst: &mut [DevByte],
+        src: Option<&[DevByte]>,
+        root: usize,
+        stream: &Stream,
+    ) {
+        self.check_stream(stream);
+        let src = src.map_or(dst.as_ptr(), |src| {
+            assert_eq!(src.len(), dst.len());
+            src.as_ptr()
+        });
+        mccl!(mcclBroadcast(
+            src.cast(),
+            dst.as_mut_ptr().cast(),
+            count::<T>(dst),
+            T::DATA_TYPE,
+            root as _,
+            self.comm,
+            stream.as_raw() as _
+        ));
+    }
+}
+
+impl Communicator {
+    /// 集合通信只能在通信组所在设备的流上发起。
+    #[inline]
+    fn check_stream(&self, stream: &Stream) {
+        assert_eq!(
+            unsafe { stream.ctx().dev().as_raw() },
+            self.dev,
+            "stream is not on the communicator's device"
+        );
+    }
+}
+
+impl Drop for Communicator {
+    #[inline]
+    fn drop(&mut self) {
+        mccl!(mcclCommDestroy(self.comm));
+    }
+}
+
+impl AsRaw for Communicator {
+    type Raw = mcclComm_t;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.comm
+    }
+}
+
+#[inline]
+fn count<T: CclType>(buf: &[DevByte]) -> usize {
+    assert_eq!(buf.len() % size_of::<T>(), 0);
+    buf.len() / size_of::<T>()
+}
+
+#[test]
+fn test_all_reduce() {
+    use runtime::{memcpy_d2h, ContextGroup, NoDevice};
+    use std::sync::Mutex;
+
+    if let Err(NoDevice) = runtime::init() {
+        return;
+    }
+    let devices = Device::all().collect::<Vec<_>>();
+    let comms = Communicator::init_all(&devices)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    let comms = Mutex::new(comms);
+    let group = ContextGroup::new(devices);
+    let n = group.len() as f32;
+    group.run(|member| {
+        let comm = comms.lock().unwrap()[member.index()].take().unwrap();
+        let stream = member.stream();
+        let mut buf = member.ctx().malloc::<f32>(64);
+        stream.memcpy_h2d(&mut buf, &[1.0f32; 64]);
+        comm.all_reduce::<f32>(&mut buf, None, ReduceOp::Sum, stream);
+        stream.synchronize();
+
+        let mut host = [0.0f32; 64];
+        memcpy_d2h(&mut host, &buf);
+        assert!(host.iter().all(|&x| x == n));
+    });
+}
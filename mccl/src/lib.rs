@@ -0,0 +1,109 @@
+#![cfg(detected_mx)]
+
+#[macro_use]
+#[allow(
+    unused,
+    non_upper_case_globals,
+    non_camel_case_types,
+    non_snake_case,
+    clippy::useless_transmute,
+    clippy::too_many_arguments
+)]
+pub mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+    #[macro_export]
+    macro_rules! mccl {
+        ($f:expr) => {{
+            #[allow(unused_imports)]
+            use $crate::bindings::*;
+            #[allow(unused_unsafe, clippy::macro_metavars_in_unsafe)]
+            let result = unsafe { $f };
+            assert_eq!(result, mcclResult_t::mcclSuccess);
+        }};
+    }
+}
+
+mod communicator;
+mod unique_id;
+
+pub use communicator::Communicator;
+pub use unique_id::{InvalidUniqueId, UniqueId};
+
+use bindings::{mcclDataType_t, mcclRedOp_t};
+use half::{bf16, f16};
+
+/// 可参与集合通信的元素类型。
+pub trait CclType: Copy + 'static {
+    const DATA_TYPE: mcclDataType_t;
+}
+
+macro_rules! impl_ccl_type {
+    ($($ty:ty => $data_type:ident),+ $(,)?) => {
+        $(
+            impl CclType for $ty {
+                const DATA_TYPE: mcclDataType_t = mcclDataType_t::$data_type;
+            }
+        )+
+    };
+}
+
+impl_ccl_type! {
+    i8 => mcclInt8,
+    u8 => mcclUint8,
+    i32 => mcclInt32,
+    u32 => mcclUint32,
+    i64 => mcclInt64,
+    u64 => mcclUint64,
+    f16 => mcclFloat16,
+    bf16 => mcclBfloat16,
+    f32 => mcclFloat32,
+    f64 => mcclFloat64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ReduceOp {
+    Sum,
+    Prod,
+    Max,
+    Min,
+    Avg,
+}
+
+impl From<ReduceOp> for mcclRedOp_t {
+    #[inline]
+    fn from(op: ReduceOp) -> Self {
+        match op {
+            ReduceOp::Sum => Self::mcclSum,
+            ReduceOp::Prod => Self::mcclProd,
+            ReduceOp::Max => Self::mcclMax,
+            ReduceOp::Min => Self::mcclMin,
+            ReduceOp::Avg => Self::mcclAvg,
+        }
+    }
+}
+
+/// 将 `f` 中发起的集合通信合并为一次启动。
+///
+/// 单进程驱动多卡时，对各卡的集合通信必须在同一个组中发起，否则会互相等待而死锁。
+///
+/// `f` panic 时组也会结束。
+pub fn group<T>(f: impl FnOnce() -> T) -> T {
+    mccl!(mcclGroupStart());
+    let _guard = GroupGuard;
+    f()
+}
+
+/// 析构时（包括栈展开时）结束通信组。
+struct GroupGuard;
+
+impl Drop for GroupGuard {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            // 展开时不能再次 panic
+            let _ = unsafe { bindings::mcclGroupEnd() };
+        } else {
+            mccl!(mcclGroupEnd());
+        }
+    }
+}
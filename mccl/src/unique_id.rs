@@ -0,0 +1,89 @@
+use crate::bindings::mcclUniqueId;
+use std::{fmt, str::FromStr};
+
+/// 通信组的唯一标识，由一个进程创建后分发给所有参与者。
+///
+/// 可以以十六进制文本或字节形式传递给其他进程。
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UniqueId([u8; Self::LEN]);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidUniqueId;
+
+impl UniqueId {
+    pub const LEN: usize = 128;
+
+    /// 向通信库申请一个新的标识。
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let mut id = mcclUniqueId {
+            internal: [0; Self::LEN],
+        };
+        mccl!(mcclGetUniqueId(&mut id));
+        Self(id.internal.map(|b| b as _))
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8; Self::LEN] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InvalidUniqueId> {
+        bytes.try_into().map(Self).map_err(|_| InvalidUniqueId)
+    }
+
+    #[inline]
+    pub(crate) fn to_raw(self) -> mcclUniqueId {
+        mcclUniqueId {
+            internal: self.0.map(|b| b as _),
+        }
+    }
+}
+
+impl fmt::Debug for UniqueId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UniqueId({self})")
+    }
+}
+
+impl fmt::Display for UniqueId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.as_bytes() {
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for UniqueId {
+    type Err = InvalidUniqueId;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != Self::LEN * 2 {
+            return Err(InvalidUniqueId);
+        }
+        let bytes = s
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                let pair = std::str::from_utf8(pair).map_err(|_| InvalidUniqueId)?;
+                u8::from_str_radix(pair, 16).map_err(|_| InvalidUniqueId)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[test]
+fn test_unique_id() {
+    let mut bytes = [0u8; UniqueId::LEN];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = (i * 3) as u8;
+    }
+    let id = UniqueId::from_bytes(&bytes).unwrap();
+    assert_eq!(id.as_bytes(), &bytes);
+    assert_eq!(id.to_string().parse(), Ok(id));
+    assert_eq!(UniqueId::from_bytes(&bytes[1..]), Err(InvalidUniqueId));
+    assert_eq!("00".parse::<UniqueId>(), Err(InvalidUniqueId));
+}
@@ -0,0 +1 @@
+#include <mccl/mccl.h>
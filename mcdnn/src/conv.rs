@@ -0,0 +1,204 @@
+use crate::{
+    bindings::{mcdnnConvolutionFwdAlgoPerf_t, mcdnnConvolutionFwdAlgo_t, mcdnnStatus_t},
+    ConvDescriptor, Dnn, FilterDescriptor, TensorDescriptor, ONE, ZERO,
+};
+use context_spore::AsRaw;
+use runtime::{DevByte, Stream};
+use std::{mem::zeroed, time::Duration};
+
+/// 一种卷积算法的测量结果。
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ConvAlgoPerf {
+    pub algo: mcdnnConvolutionFwdAlgo_t,
+    pub time: Duration,
+    /// 所需的工作空间，以字节计。
+    pub workspace: usize,
+}
+
+/// 选定了算法的前向卷积 `y = conv(x, w)`。
+pub struct ConvForward<'a> {
+    x: &'a TensorDescriptor,
+    w: &'a FilterDescriptor,
+    conv: &'a ConvDescriptor,
+    y: &'a TensorDescriptor,
+    algo: mcdnnConvolutionFwdAlgo_t,
+    workspace: usize,
+}
+
+impl ConvForward<'_> {
+    #[inline]
+    pub fn algo(&self) -> mcdnnConvolutionFwdAlgo_t {
+        self.algo
+    }
+
+    /// 所需的工作空间，以字节计。
+    #[inline]
+    pub fn workspace_size(&self) -> usize {
+        self.workspace
+    }
+}
+
+impl Dnn<'_> {
+    /// 在设备上实测所有可用的前向卷积算法，按耗时从短到长排列。
+    pub fn find_conv_forward(
+        &self,
+        x: &TensorDescriptor,
+        w: &FilterDescriptor,
+        conv: &ConvDescriptor,
+        y: &TensorDescriptor,
+    ) -> Vec<ConvAlgoPerf> {
+        let mut max = 0;
+        mcdnn!(mcdnnGetConvolutionForwardAlgorithmMaxCount(
+            self.as_raw(),
+            &mut max
+        ));
+        let mut perfs = vec![unsafe { zeroed::<mcdnnConvolutionFwdAlgoPerf_t>() }; max as _];
+        let mut n = 0;
+        mcdnn!(mcdnnFindConvolutionForwardAlgorithm(
+            self.as_raw(),
+            x.as_raw(),
+            w.as_raw(),
+            conv.as_raw(),
+            y.as_raw(),
+            max,
+            &mut n,
+            perfs.as_mut_ptr()
+        ));
+        perfs.truncate(n as _);
+        perfs
+            .into_iter()
+            .filter(|p| p.status == mcdnnStatus_t::MCDNN_STATUS_SUCCESS)
+            .map(|p| ConvAlgoPerf {
+                algo: p.algo,
+                time: Duration::from_secs_f32(p.time.max(0.) / 1000.),
+                workspace: p.memory,
+            })
+            .collect()
+    }
+
+    /// 检查形状并选出最快的算法。
+    pub fn conv_forward_plan<'a>(
+        &self,
+        x: &'a TensorDescriptor,
+        w: &'a FilterDescriptor,
+        conv: &'a ConvDescriptor,
+        y: &'a TensorDescriptor,
+    ) -> ConvForward<'a> {
+        assert_eq!(conv.output_dims(x, w), y.dims());
+        let perf = self
+            .find_conv_forward(x, w, conv, y)
+            .into_iter()
+            .next()
+            .expect("No convolution algorithm available");
+        self.conv_forward_with_algo(x, w, conv, y, perf.algo)
+    }
+
+    /// 使用指定的算法，不进行测量。
+    pub fn conv_forward_with_algo<'a>(
+        &self,
+        x: &'a TensorDescriptor,
+        w: &'a FilterDescriptor,
+        conv: &'a ConvDescriptor,
+        y: &'a TensorDescriptor,
+        algo: mcdnnConvolutionFwdAlgo_t,
+    ) -> ConvForward<'a> {
+        let mut workspace = 0;
+        mcdnn!(mcdnnGetConvolutionForwardWorkspaceSize(
+            self.as_raw(),
+            x.as_raw(),
+            w.as_raw(),
+            conv.as_raw(),
+            y.as_raw(),
+            algo,
+            &mut workspace
+        ));
+        ConvForward {
+            x,
+            w,
+            conv,
+            y,
+            algo,
+            workspace,
+        }
+    }
+
+    /// 执行前向卷积。
+    ///
+    /// 未提供工作空间时在 `stream` 上临时申请，计算完成后释放。
+    pub fn conv_forward(
+        &self,
+        stream: &Stream,
+        plan: &ConvForward,
+        x: &[DevByte],
+        w: &[DevByte],
+        y: &mut [DevByte],
+        workspace: Option<&mut [DevByte]>,
+    ) {
+        assert!(x.len() >= plan.x.size());
+        assert!(w.len() >= plan.w.size());
+        assert!(y.len() >= plan.y.size());
+
+        let mut temp = None;
+        let workspace = match workspace {
+            Some(ws) => {
+                assert!(ws.len() >= plan.workspace);
+                ws.as_mut_ptr()
+            }
+            None if plan.workspace == 0 => std::ptr::null_mut(),
+            None => temp
+                .insert(stream.ctx().malloc::<u8>(plan.workspace))
+                .as_mut_ptr(),
+        };
+        mcdnn!(mcdnnConvolutionForward(
+            self.bind(stream),
+            (&ONE as *const f32).cast(),
+            plan.x.as_raw(),
+            x.as_ptr().cast(),
+            plan.w.as_raw(),
+            w.as_ptr().cast(),
+            plan.conv.as_raw(),
+            plan.algo,
+            workspace.cast(),
+            plan.workspace,
+            (&ZERO as *const f32).cast(),
+            plan.y.as_raw(),
+            y.as_mut_ptr().cast()
+        ));
+        if let Some(temp) = temp {
+            temp.drop_on(stream);
+        }
+    }
+}
+
+#[test]
+fn test_conv() {
+    use runtime::{memcpy_d2h, memcpy_h2d, Device, NoDevice};
+
+    if let Err(NoDevice) = runtime::init() {
+        return;
+    }
+    Device::new(0).context().apply(|ctx| {
+        let stream = ctx.stream();
+        let dnn = Dnn::new(ctx);
+
+        // 3x3 的输入与全 1 的 2x2 卷积核，得到 2x2 的窗口和
+        let x = TensorDescriptor::new::<f32>(&[1, 1, 3, 3]).unwrap();
+        let w = FilterDescriptor::new::<f32>(&[1, 1, 2, 2]).unwrap();
+        let conv = ConvDescriptor::new(&[0, 0], &[1, 1], &[1, 1]).unwrap();
+        let y = TensorDescriptor::new::<f32>(&conv.output_dims(&x, &w)).unwrap();
+        assert_eq!(y.dims(), [1, 1, 2, 2]);
+
+        let plan = dnn.conv_forward_plan(&x, &w, &conv, &y);
+        let mut dx = ctx.malloc::<f32>(9);
+        let mut dw = ctx.malloc::<f32>(4);
+        let mut dy = ctx.malloc::<f32>(4);
+        memcpy_h2d(&mut dx, &[1., 2., 3., 4., 5., 6., 7., 8., 9.0f32]);
+        memcpy_h2d(&mut dw, &[1.0f32; 4]);
+        dnn.conv_forward(&stream, &plan, &dx, &dw, &mut dy, None);
+        stream.synchronize();
+
+        let mut host = [0.0f32; 4];
+        memcpy_d2h(&mut host, &dy);
+        assert_eq!(host, [12., 16., 24., 28.]);
+    });
+}
@@ -0,0 +1,264 @@
+use crate::{
+    bindings::{
+        mcdnnConvolutionDescriptor_t, mcdnnFilterDescriptor_t, mcdnnPoolingDescriptor_t,
+        mcdnnTensorDescriptor_t,
+    },
+    DnnError, DnnType, PoolingMode,
+};
+use context_spore::AsRaw;
+use std::{ffi::c_int, mem::size_of, ptr::null_mut};
+
+/// 张量描述符，记录形状、跨度和元素类型。
+pub struct TensorDescriptor {
+    raw: mcdnnTensorDescriptor_t,
+    dims: Vec<usize>,
+    strides: Vec<usize>,
+    elem_size: usize,
+}
+
+/// 卷积核描述符，形状为 `[k, c, ...]`，按行主序连续存储。
+pub struct FilterDescriptor {
+    raw: mcdnnFilterDescriptor_t,
+    dims: Vec<usize>,
+    elem_size: usize,
+}
+
+/// 卷积描述符，记录每个空间维度的填充、步长和膨胀。
+pub struct ConvDescriptor(mcdnnConvolutionDescriptor_t);
+
+pub struct PoolingDescriptor(mcdnnPoolingDescriptor_t);
+
+impl TensorDescriptor {
+    /// 行主序连续存储的张量，如 `[n, c, h, w]`。
+    pub fn new<T: DnnType>(dims: &[usize]) -> Result<Self, DnnError> {
+        let mut strides = vec![1; dims.len()];
+        for i in (1..dims.len()).rev() {
+            strides[i - 1] = strides[i] * dims[i];
+        }
+        Self::with_strides::<T>(dims, &strides)
+    }
+
+    /// 每个维度的跨度以元素计。
+    pub fn with_strides<T: DnnType>(dims: &[usize], strides: &[usize]) -> Result<Self, DnnError> {
+        assert_eq!(dims.len(), strides.len());
+        let (dims_, strides_) = (to_ints(dims)?, to_ints(strides)?);
+        let mut raw = null_mut();
+        mcdnn!(mcdnnCreateTensorDescriptor(&mut raw));
+        let ans = Self {
+            raw,
+            dims: dims.to_vec(),
+            strides: strides.to_vec(),
+            elem_size: size_of::<T>(),
+        };
+        mcdnn!(mcdnnSetTensorNdDescriptor(
+            raw,
+            T::DATA_TYPE,
+            dims_.len() as _,
+            dims_.as_ptr(),
+            strides_.as_ptr()
+        ));
+        Ok(ans)
+    }
+
+    /// 空间批归一化的逐通道参数描述符，形状为 `[1, c, 1, ...]`，类型为 `f32`。
+    pub(crate) fn batch_norm_param(x: &Self) -> Self {
+        use crate::bindings::mcdnnBatchNormMode_t::MCDNN_BATCHNORM_SPATIAL;
+        let mut dims = vec![1; x.dims.len()];
+        dims[1] = x.dims[1];
+        let mut strides = vec![1; dims.len()];
+        strides[0] = dims[1];
+        let mut raw = null_mut();
+        mcdnn!(mcdnnCreateTensorDescriptor(&mut raw));
+        let ans = Self {
+            raw,
+            dims,
+            strides,
+            elem_size: size_of::<f32>(),
+        };
+        mcdnn!(mcdnnDeriveBNTensorDescriptor(
+            raw,
+            x.raw,
+            MCDNN_BATCHNORM_SPATIAL
+        ));
+        ans
+    }
+
+    #[inline]
+    pub fn dims(&self) -> &[usize] {
+        &self.dims
+    }
+
+    #[inline]
+    pub fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    /// 张量覆盖的存储大小，以字节计。
+    pub fn size(&self) -> usize {
+        if self.dims.contains(&0) {
+            return 0;
+        }
+        let last = self
+            .dims
+            .iter()
+            .zip(&self.strides)
+            .map(|(d, s)| (d - 1) * s)
+            .sum::<usize>();
+        (last + 1) * self.elem_size
+    }
+}
+
+impl FilterDescriptor {
+    pub fn new<T: DnnType>(dims: &[usize]) -> Result<Self, DnnError> {
+        use crate::bindings::mcdnnTensorFormat_t::MCDNN_TENSOR_NCHW;
+        let dims_ = to_ints(dims)?;
+        let mut raw = null_mut();
+        mcdnn!(mcdnnCreateFilterDescriptor(&mut raw));
+        let ans = Self {
+            raw,
+            dims: dims.to_vec(),
+            elem_size: size_of::<T>(),
+        };
+        mcdnn!(mcdnnSetFilterNdDescriptor(
+            raw,
+            T::DATA_TYPE,
+            MCDNN_TENSOR_NCHW,
+            dims_.len() as _,
+            dims_.as_ptr()
+        ));
+        Ok(ans)
+    }
+
+    #[inline]
+    pub fn dims(&self) -> &[usize] {
+        &self.dims
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.dims.iter().product::<usize>() * self.elem_size
+    }
+}
+
+impl ConvDescriptor {
+    /// 互相关卷积，以 `f32` 累加。三个参数的长度即空间维数。
+    pub fn new(pad: &[usize], stride: &[usize], dilation: &[usize]) -> Result<Self, DnnError> {
+        use crate::bindings::{
+            mcdnnConvolutionMode_t::MCDNN_CROSS_CORRELATION, mcdnnDataType_t::*,
+        };
+        assert_eq!(pad.len(), stride.len());
+        assert_eq!(pad.len(), dilation.len());
+        let (pad, stride, dilation) = (to_ints(pad)?, to_ints(stride)?, to_ints(dilation)?);
+        let mut raw = null_mut();
+        mcdnn!(mcdnnCreateConvolutionDescriptor(&mut raw));
+        let ans = Self(raw);
+        mcdnn!(mcdnnSetConvolutionNdDescriptor(
+            raw,
+            pad.len() as _,
+            pad.as_ptr(),
+            stride.as_ptr(),
+            dilation.as_ptr(),
+            MCDNN_CROSS_CORRELATION,
+            MCDNN_DATA_FLOAT
+        ));
+        Ok(ans)
+    }
+
+    /// 以 `groups` 分组卷积。
+    pub fn groups(self, groups: usize) -> Result<Self, DnnError> {
+        let groups = c_int::try_from(groups).map_err(|_| DnnError::TooLarge)?;
+        mcdnn!(mcdnnSetConvolutionGroupCount(self.0, groups));
+        Ok(self)
+    }
+
+    /// 对输入 `x` 和卷积核 `w` 卷积得到的输出形状。
+    pub fn output_dims(&self, x: &TensorDescriptor, w: &FilterDescriptor) -> Vec<usize> {
+        let mut dims = vec![0 as c_int; x.dims.len()];
+        mcdnn!(mcdnnGetConvolutionNdForwardOutputDim(
+            self.0,
+            x.raw,
+            w.raw,
+            dims.len() as _,
+            dims.as_mut_ptr()
+        ));
+        dims.into_iter().map(|d| d as _).collect()
+    }
+}
+
+impl PoolingDescriptor {
+    /// 三个参数的长度即空间维数。
+    pub fn new(
+        mode: PoolingMode,
+        window: &[usize],
+        pad: &[usize],
+        stride: &[usize],
+    ) -> Result<Self, DnnError> {
+        use crate::bindings::mcdnnNanPropagation_t::MCDNN_NOT_PROPAGATE_NAN;
+        assert_eq!(window.len(), pad.len());
+        assert_eq!(window.len(), stride.len());
+        let (window, pad, stride) = (to_ints(window)?, to_ints(pad)?, to_ints(stride)?);
+        let mut raw = null_mut();
+        mcdnn!(mcdnnCreatePoolingDescriptor(&mut raw));
+        let ans = Self(raw);
+        mcdnn!(mcdnnSetPoolingNdDescriptor(
+            raw,
+            mode.into(),
+            MCDNN_NOT_PROPAGATE_NAN,
+            window.len() as _,
+            window.as_ptr(),
+            pad.as_ptr(),
+            stride.as_ptr()
+        ));
+        Ok(ans)
+    }
+
+    /// 对输入 `x` 池化得到的输出形状。
+    pub fn output_dims(&self, x: &TensorDescriptor) -> Vec<usize> {
+        let mut dims = vec![0 as c_int; x.dims.len()];
+        mcdnn!(mcdnnGetPoolingNdForwardOutputDim(
+            self.0,
+            x.raw,
+            dims.len() as _,
+            dims.as_mut_ptr()
+        ));
+        dims.into_iter().map(|d| d as _).collect()
+    }
+}
+
+macro_rules! impl_descriptor {
+    ($ty:ty, $raw:ty, $destroy:ident; $($field:tt)+) => {
+        impl Drop for $ty {
+            #[inline]
+            fn drop(&mut self) {
+                mcdnn!($destroy(self.$($field)+));
+            }
+        }
+
+        impl AsRaw for $ty {
+            type Raw = $raw;
+            #[inline]
+            unsafe fn as_raw(&self) -> Self::Raw {
+                self.$($field)+
+            }
+        }
+    };
+}
+
+impl_descriptor!(TensorDescriptor, mcdnnTensorDescriptor_t, mcdnnDestroyTensorDescriptor; raw);
+impl_descriptor!(FilterDescriptor, mcdnnFilterDescriptor_t, mcdnnDestroyFilterDescriptor; raw);
+impl_descriptor!(ConvDescriptor, mcdnnConvolutionDescriptor_t, mcdnnDestroyConvolutionDescriptor; 0);
+impl_descriptor!(PoolingDescriptor, mcdnnPoolingDescriptor_t, mcdnnDestroyPoolingDescriptor; 0);
+
+#[inline]
+fn to_ints(values: &[usize]) -> Result<Vec<c_int>, DnnError> {
+    values
+        .iter()
+        .map(|&v| c_int::try_from(v).map_err(|_| DnnError::TooLarge))
+        .collect()
+}
+
+#[test]
+fn test_to_ints() {
+    assert_eq!(to_ints(&[1, 2, 3]), Ok(vec![1, 2, 3]));
+    assert_eq!(to_ints(&[1, usize::MAX]), Err(DnnError::TooLarge));
+}
@@ -0,0 +1,36 @@
+use crate::bindings::mcdnnHandle_t;
+use context_spore::{impl_spore, AsRaw};
+use runtime::{CurrentCtx, Stream};
+use std::{marker::PhantomData, ptr::null_mut};
+
+impl_spore!(Dnn and DnnSpore by (CurrentCtx, mcdnnHandle_t));
+
+impl<'ctx> Dnn<'ctx> {
+    #[inline]
+    pub fn new(ctx: &'ctx CurrentCtx) -> Self {
+        let mut handle = null_mut();
+        mcdnn!(mcdnnCreate(&mut handle));
+        Self(unsafe { ctx.wrap_raw(handle) }, PhantomData)
+    }
+
+    #[inline]
+    pub(crate) fn bind(&self, stream: &Stream) -> mcdnnHandle_t {
+        mcdnn!(mcdnnSetStream(self.0.rss, stream.as_raw() as _));
+        self.0.rss
+    }
+}
+
+impl Drop for Dnn<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        mcdnn!(mcdnnDestroy(self.0.rss));
+    }
+}
+
+impl AsRaw for Dnn<'_> {
+    type Raw = mcdnnHandle_t;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0.rss
+    }
+}
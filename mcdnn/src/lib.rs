@@ -0,0 +1,67 @@
+#![cfg(detected_mx)]
+
+#[macro_use]
+#[allow(
+    unused,
+    non_upper_case_globals,
+    non_camel_case_types,
+    non_snake_case,
+    clippy::useless_transmute,
+    clippy::too_many_arguments
+)]
+pub mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+    #[macro_export]
+    macro_rules! mcdnn {
+        ($f:expr) => {{
+            #[allow(unused_imports)]
+            use $crate::bindings::*;
+            #[allow(unused_unsafe, clippy::macro_metavars_in_unsafe)]
+            let status = unsafe { $f };
+            assert_eq!(status, mcdnnStatus_t::MCDNN_STATUS_SUCCESS);
+        }};
+    }
+}
+
+mod conv;
+mod descriptor;
+mod handle;
+mod ops;
+
+pub use conv::{ConvAlgoPerf, ConvForward};
+pub use descriptor::{ConvDescriptor, FilterDescriptor, PoolingDescriptor, TensorDescriptor};
+pub use handle::{Dnn, DnnSpore};
+pub use ops::{BatchNormParams, PoolingMode, SoftmaxAlgo, SoftmaxMode};
+
+use bindings::mcdnnDataType_t;
+use half::{bf16, f16};
+
+/// 可参与 DNN 计算的元素类型。
+pub trait DnnType: Copy + 'static {
+    const DATA_TYPE: mcdnnDataType_t;
+}
+
+impl DnnType for f32 {
+    const DATA_TYPE: mcdnnDataType_t = mcdnnDataType_t::MCDNN_DATA_FLOAT;
+}
+
+impl DnnType for f16 {
+    const DATA_TYPE: mcdnnDataType_t = mcdnnDataType_t::MCDNN_DATA_HALF;
+}
+
+impl DnnType for bf16 {
+    const DATA_TYPE: mcdnnDataType_t = mcdnnDataType_t::MCDNN_DATA_BFLOAT16;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DnnError {
+    /// 张量的维数少于算子要求的最低维数。
+    RankTooLow { rank: usize, min: usize },
+    /// 尺寸超过了库接口能够表示的范围。
+    TooLarge,
+}
+
+/// 库接口的缩放系数，`y = ONE * op(x) + ZERO * y`。
+const ONE: f32 = 1.;
+const ZERO: f32 = 0.;
@@ -0,0 +1,193 @@
+use crate::{
+    bindings::{mcdnnPoolingMode_t, mcdnnSoftmaxAlgorithm_t, mcdnnSoftmaxMode_t},
+    Dnn, DnnError, PoolingDescriptor, TensorDescriptor, ONE, ZERO,
+};
+use context_spore::AsRaw;
+use runtime::{DevByte, Stream};
+use std::mem::size_of;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub enum SoftmaxAlgo {
+    /// 不减去最大值，可能溢出。
+    Fast,
+    #[default]
+    Accurate,
+    /// 输出 log softmax。
+    Log,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub enum SoftmaxMode {
+    /// 对每个样本的 `c, h, w` 整体归一化。
+    Instance,
+    /// 对每个空间位置沿 `c` 归一化。
+    #[default]
+    Channel,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PoolingMode {
+    Max,
+    /// 平均池化，填充位置计入分母。
+    AvgIncludePadding,
+    /// 平均池化，填充位置不计入分母。
+    AvgExcludePadding,
+}
+
+impl From<PoolingMode> for mcdnnPoolingMode_t {
+    #[inline]
+    fn from(mode: PoolingMode) -> Self {
+        match mode {
+            PoolingMode::Max => Self::MCDNN_POOLING_MAX,
+            PoolingMode::AvgIncludePadding => Self::MCDNN_POOLING_AVERAGE_COUNT_INCLUDE_PADDING,
+            PoolingMode::AvgExcludePadding => Self::MCDNN_POOLING_AVERAGE_COUNT_EXCLUDE_PADDING,
+        }
+    }
+}
+
+/// 推理时批归一化的逐通道参数，形状均为 `[1, c, 1, 1]`，类型为 `f32`。
+#[derive(Clone, Copy)]
+pub struct BatchNormParams<'a> {
+    pub scale: &'a [DevByte],
+    pub bias: &'a [DevByte],
+    pub mean: &'a [DevByte],
+    pub var: &'a [DevByte],
+    pub epsilon: f64,
+}
+
+impl Dnn<'_> {
+    /// `x` 和 `y` 具有相同的形状 `desc`。
+    pub fn softmax(
+        &self,
+        stream: &Stream,
+        desc: &TensorDescriptor,
+        algo: SoftmaxAlgo,
+        mode: SoftmaxMode,
+        x: &[DevByte],
+        y: &mut [DevByte],
+    ) {
+        assert!(x.len() >= desc.size());
+        assert!(y.len() >= desc.size());
+        let algo = match algo {
+            SoftmaxAlgo::Fast => mcdnnSoftmaxAlgorithm_t::MCDNN_SOFTMAX_FAST,
+            SoftmaxAlgo::Accurate => mcdnnSoftmaxAlgorithm_t::MCDNN_SOFTMAX_ACCURATE,
+            SoftmaxAlgo::Log => mcdnnSoftmaxAlgorithm_t::MCDNN_SOFTMAX_LOG,
+        };
+        let mode = match mode {
+            SoftmaxMode::Instance => mcdnnSoftmaxMode_t::MCDNN_SOFTMAX_MODE_INSTANCE,
+            SoftmaxMode::Channel => mcdnnSoftmaxMode_t::MCDNN_SOFTMAX_MODE_CHANNEL,
+        };
+        mcdnn!(mcdnnSoftmaxForward(
+            self.bind(stream),
+            algo,
+            mode,
+            (&ONE as *const f32).cast(),
+            desc.as_raw(),
+            x.as_ptr().cast(),
+            (&ZERO as *const f32).cast(),
+            desc.as_raw(),
+            y.as_mut_ptr().cast()
+        ));
+    }
+
+    /// `y` 的形状应为 [`PoolingDescriptor::output_dims`]。
+    pub fn pooling(
+        &self,
+        stream: &Stream,
+        pool: &PoolingDescriptor,
+        x_desc: &TensorDescriptor,
+        x: &[DevByte],
+        y_desc: &TensorDescriptor,
+        y: &mut [DevByte],
+    ) {
+        assert_eq!(pool.output_dims(x_desc), y_desc.dims());
+        assert!(x.len() >= x_desc.size());
+        assert!(y.len() >= y_desc.size());
+        mcdnn!(mcdnnPoolingForward(
+            self.bind(stream),
+            pool.as_raw(),
+            (&ONE as *const f32).cast(),
+            x_desc.as_raw(),
+            x.as_ptr().cast(),
+            (&ZERO as *const f32).cast(),
+            y_desc.as_raw(),
+            y.as_mut_ptr().cast()
+        ));
+    }
+
+    /// 推理时的空间批归一化，`x` 和 `y` 具有相同的形状 `desc`，`desc` 至少有 2 维。
+    pub fn batch_norm(
+        &self,
+        stream: &Stream,
+        desc: &TensorDescriptor,
+        x: &[DevByte],
+        y: &mut [DevByte],
+        params: BatchNormParams,
+    ) -> Result<(), DnnError> {
+        use crate::bindings::mcdnnBatchNormMode_t::MCDNN_BATCHNORM_SPATIAL;
+
+        let dims = desc.dims();
+        if dims.len() < 2 {
+            return Err(DnnError::RankTooLow {
+                rank: dims.len(),
+                min: 2,
+            });
+        }
+        assert!(x.len() >= desc.size());
+        assert!(y.len() >= desc.size());
+        let channels = dims[1] * size_of::<f32>();
+        for p in [params.scale, params.bias, params.mean, params.var] {
+            assert!(p.len() >= channels);
+        }
+
+        let param_desc = TensorDescriptor::batch_norm_param(desc);
+        mcdnn!(mcdnnBatchNormalizationForwardInference(
+            self.bind(stream),
+            MCDNN_BATCHNORM_SPATIAL,
+            (&ONE as *const f32).cast(),
+            (&ZERO as *const f32).cast(),
+            desc.as_raw(),
+            x.as_ptr().cast(),
+            desc.as_raw(),
+            y.as_mut_ptr().cast(),
+            param_desc.as_raw(),
+            params.scale.as_ptr().cast(),
+            params.bias.as_ptr().cast(),
+            params.mean.as_ptr().cast(),
+            params.var.as_ptr().cast(),
+            params.epsilon
+        ));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_softmax() {
+    use runtime::{memcpy_d2h, memcpy_h2d, Device, NoDevice};
+
+    if let Err(NoDevice) = runtime::init() {
+        return;
+    }
+    Device::new(0).context().apply(|ctx| {
+        let stream = ctx.stream();
+        let dnn = Dnn::new(ctx);
+        let desc = TensorDescriptor::new::<f32>(&[1, 4, 1, 1]).unwrap();
+
+        let mut x = ctx.malloc::<f32>(4);
+        let mut y = ctx.malloc::<f32>(4);
+        memcpy_h2d(&mut x, &[1.0f32; 4]);
+        dnn.softmax(
+            &stream,
+            &desc,
+            SoftmaxAlgo::Accurate,
+            SoftmaxMode::Channel,
+            &x,
+            &mut y,
+        );
+        stream.synchronize();
+
+        let mut host = [0.0f32; 4];
+        memcpy_d2h(&mut host, &y);
+        assert!(host.iter().all(|&p| (p - 0.25).abs() < 1e-6));
+    });
+}
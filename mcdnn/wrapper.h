@@ -0,0 +1 @@
+#include <mcdnn/mcdnn.h>
@@ -0,0 +1,50 @@
+use crate::{FftShape, Plan};
+use runtime::CurrentCtx;
+use std::collections::HashMap;
+
+/// 按形状缓存的计划，同一形状只创建一次。
+pub struct PlanCache<'ctx> {
+    ctx: &'ctx CurrentCtx,
+    plans: HashMap<FftShape, Plan<'ctx>>,
+}
+
+impl<'ctx> PlanCache<'ctx> {
+    #[inline]
+    pub fn new(ctx: &'ctx CurrentCtx) -> Self {
+        Self {
+            ctx,
+            plans: HashMap::new(),
+        }
+    }
+
+    /// 取出 `shape` 对应的计划，不存在时创建。
+    pub fn get(&mut self, shape: &FftShape) -> &Plan<'ctx> {
+        self.plans
+            .entry(shape.clone())
+            .or_insert_with(|| Plan::new(self.ctx, shape.clone()))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.plans.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.plans.is_empty()
+    }
+
+    /// 缓存的计划所需的最大工作空间，可据此申请一块在所有计划间共享的工作空间。
+    pub fn max_workspace_size(&self) -> usize {
+        self.plans
+            .values()
+            .map(Plan::workspace_size)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.plans.clear()
+    }
+}
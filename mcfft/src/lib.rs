@@ -0,0 +1,170 @@
+#![cfg(detected_mx)]
+
+#[macro_use]
+#[allow(
+    unused,
+    non_upper_case_globals,
+    non_camel_case_types,
+    non_snake_case,
+    clippy::useless_transmute,
+    clippy::too_many_arguments
+)]
+pub mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+    #[macro_export]
+    macro_rules! mcfft {
+        ($f:expr) => {{
+            #[allow(unused_imports)]
+            use $crate::bindings::*;
+            #[allow(unused_unsafe, clippy::macro_metavars_in_unsafe)]
+            let result = unsafe { $f };
+            assert_eq!(result, mcfftResult_t::MCFFT_SUCCESS);
+        }};
+    }
+}
+
+mod cache;
+mod plan;
+
+pub use cache::PlanCache;
+pub use plan::{Plan, PlanSpore};
+
+use bindings::mcfftType_t;
+use std::{ffi::c_int, mem::size_of};
+
+/// 变换的输入输出类型，`Z`/`D` 为双精度。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FftType {
+    /// 实数到复数，只输出非负频率的一半。
+    R2C,
+    /// 复数到实数，[`FftType::R2C`] 的逆变换。
+    C2R,
+    C2C,
+    D2Z,
+    Z2D,
+    Z2Z,
+}
+
+impl From<FftType> for mcfftType_t {
+    #[inline]
+    fn from(ty: FftType) -> Self {
+        match ty {
+            FftType::R2C => Self::MCFFT_R2C,
+            FftType::C2R => Self::MCFFT_C2R,
+            FftType::C2C => Self::MCFFT_C2C,
+            FftType::D2Z => Self::MCFFT_D2Z,
+            FftType::Z2D => Self::MCFFT_Z2D,
+            FftType::Z2Z => Self::MCFFT_Z2Z,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    Forward,
+    Inverse,
+}
+
+/// 一组连续存储的批量变换的形状，也是计划缓存的键。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FftShape {
+    dims: Vec<usize>,
+    ty: FftType,
+    batch: usize,
+    input_size: usize,
+    output_size: usize,
+}
+
+impl FftShape {
+    /// `dims` 为每个变换的长度，行主序，最多 3 维。各维长度和批数须在 `c_int` 范围内，输入输出的字节数不能溢出。
+    pub fn new(dims: &[usize], ty: FftType, batch: usize) -> Self {
+        use FftType::*;
+        assert!((1..=3).contains(&dims.len()));
+        assert!(dims.iter().all(|&d| d > 0) && batch > 0);
+        assert!(dims
+            .iter()
+            .chain([&batch])
+            .all(|&d| c_int::try_from(d).is_ok()));
+
+        let real_size = match ty {
+            R2C | C2R | C2C => size_of::<f32>(),
+            D2Z | Z2D | Z2Z => size_of::<f64>(),
+        };
+        let (last, rest) = dims.split_last().unwrap();
+        let rest = rest.iter().try_fold(batch, |acc, &d| acc.checked_mul(d));
+        // 所有批次的元素数
+        let real = rest.and_then(|n| n.checked_mul(*last)?.checked_mul(real_size));
+        // 实数变换中复数一侧的元素数，最后一维只保留 `n / 2 + 1` 个频率
+        let half = rest.and_then(|n| n.checked_mul(last / 2 + 1)?.checked_mul(real_size * 2));
+        let complex = real.and_then(|n| n.checked_mul(2));
+        let (input_size, output_size) = match ty {
+            R2C | D2Z => (real, half),
+            C2R | Z2D => (half, real),
+            C2C | Z2Z => (complex, complex),
+        };
+        Self {
+            dims: dims.to_vec(),
+            ty,
+            batch,
+            input_size: input_size.expect("transform size overflows usize"),
+            output_size: output_size.expect("transform size overflows usize"),
+        }
+    }
+
+    #[inline]
+    pub fn dims(&self) -> &[usize] {
+        &self.dims
+    }
+
+    #[inline]
+    pub fn ty(&self) -> FftType {
+        self.ty
+    }
+
+    #[inline]
+    pub fn batch(&self) -> usize {
+        self.batch
+    }
+
+    /// 是否为实数与复数之间的变换。
+    #[inline]
+    pub fn is_real(&self) -> bool {
+        !matches!(self.ty, FftType::C2C | FftType::Z2Z)
+    }
+
+    /// 输入的字节数。
+    #[inline]
+    pub fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    /// 输出的字节数。
+    #[inline]
+    pub fn output_size(&self) -> usize {
+        self.output_size
+    }
+}
+
+#[test]
+fn test_shape() {
+    let shape = FftShape::new(&[4, 6], FftType::R2C, 2);
+    assert_eq!(shape.dims(), [4, 6]);
+    assert!(shape.is_real());
+    assert_eq!(shape.input_size(), 4 * 6 * 2 * 4);
+    assert_eq!(shape.output_size(), 4 * 4 * 2 * 8);
+
+    let shape = FftShape::new(&[5], FftType::Z2D, 1);
+    assert_eq!(shape.input_size(), 3 * 16);
+    assert_eq!(shape.output_size(), 5 * 8);
+
+    let shape = FftShape::new(&[2, 2, 2], FftType::C2C, 3);
+    assert!(!shape.is_real());
+    assert_eq!(shape.input_size(), shape.output_size());
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn test_shape_overflow() {
+    FftShape::new(&[1 << 30, 1 << 30, 1 << 30], FftType::C2C, 1 << 30);
+}
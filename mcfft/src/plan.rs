@@ -0,0 +1,218 @@
+use crate::{bindings::mcfftHandle, Direction, FftShape, FftType};
+use context_spore::{impl_spore, AsRaw};
+use runtime::{CurrentCtx, DevByte, DevMem, Stream};
+use std::{ffi::c_int, marker::PhantomData, ptr::null_mut};
+
+/// 变换方向，与头文件中的宏定义一致。
+const FORWARD: c_int = -1;
+const INVERSE: c_int = 1;
+
+struct RawPlan {
+    handle: mcfftHandle,
+    shape: FftShape,
+    workspace: usize,
+}
+
+impl_spore!(Plan and PlanSpore by (CurrentCtx, RawPlan));
+
+impl<'ctx> Plan<'ctx> {
+    /// 创建计划。工作空间不由库自动申请，执行时由调用者提供或临时申请。
+    pub fn new(ctx: &'ctx CurrentCtx, shape: FftShape) -> Self {
+        let mut handle = 0;
+        mcfft!(mcfftCreate(&mut handle));
+        mcfft!(mcfftSetAutoAllocation(handle, 0));
+
+        // FftShape::new 已检查各维长度和批数在 c_int 范围内
+        let mut n = shape
+            .dims()
+            .iter()
+            .map(|&d| c_int::try_from(d).unwrap())
+            .collect::<Vec<_>>();
+        let mut workspace = 0;
+        mcfft!(mcfftMakePlanMany(
+            handle,
+            n.len() as _,
+            n.as_mut_ptr(),
+            null_mut(),
+            1,
+            0,
+            null_mut(),
+            1,
+            0,
+            shape.ty().into(),
+            c_int::try_from(shape.batch()).unwrap(),
+            &mut workspace
+        ));
+        Self(
+            unsafe {
+                ctx.wrap_raw(RawPlan {
+                    handle,
+                    shape,
+                    workspace,
+                })
+            },
+            PhantomData,
+        )
+    }
+}
+
+impl Drop for Plan<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        mcfft!(mcfftDestroy(self.0.rss.handle));
+    }
+}
+
+impl AsRaw for Plan<'_> {
+    type Raw = mcfftHandle;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0.rss.handle
+    }
+}
+
+impl Plan<'_> {
+    #[inline]
+    pub fn shape(&self) -> &FftShape {
+        &self.0.rss.shape
+    }
+
+    /// 执行所需的工作空间，以字节计。
+    #[inline]
+    pub fn workspace_size(&self) -> usize {
+        self.0.rss.workspace
+    }
+
+    /// 申请一块足够执行此计划的工作空间，可在多次执行间复用。
+    #[inline]
+    pub fn malloc_workspace(&self) -> DevMem<'_> {
+        self.ctx().malloc::<u8>(self.workspace_size())
+    }
+
+    /// 在 `stream` 上执行变换。
+    ///
+    /// 实数变换的方向由类型决定，`direction` 须与之一致。
+    /// 复数到实数的变换会覆盖输入，因此输入总是以可变引用传入。
+    /// 未提供工作空间时临时申请一块，在 `stream` 上计算完成后释放。
+    pub fn exec(
+        &self,
+        stream: &Stream,
+        input: &mut [DevByte],
+        output: &mut [DevByte],
+        direction: Direction,
+        workspace: Option<&mut [DevByte]>,
+    ) {
+        let shape = self.shape();
+        assert!(input.len() >= shape.input_size());
+        assert!(output.len() >= shape.output_size());
+        self.launch(
+            stream,
+            input.as_mut_ptr(),
+            output.as_mut_ptr(),
+            direction,
+            workspace,
+        );
+    }
+
+    /// 原地执行复数到复数的变换。
+    ///
+    /// 实数变换原地执行时要求实数一侧的最后一维填充到 `2 * (n / 2 + 1)`，与连续布局不同，因此不支持。
+    pub fn exec_inplace(
+        &self,
+        stream: &Stream,
+        data: &mut [DevByte],
+        direction: Direction,
+        workspace: Option<&mut [DevByte]>,
+    ) {
+        let shape = self.shape();
+        assert!(
+            !shape.is_real(),
+            "in-place real transforms are not supported"
+        );
+        assert!(data.len() >= shape.input_size());
+        let ptr = data.as_mut_ptr();
+        self.launch(stream, ptr, ptr, direction, workspace);
+    }
+
+    fn launch(
+        &self,
+        stream: &Stream,
+        input: *mut DevByte,
+        output: *mut DevByte,
+        direction: Direction,
+        workspace: Option<&mut [DevByte]>,
+    ) {
+        use FftType::*;
+        let RawPlan {
+            handle,
+            ref shape,
+            workspace: size,
+        } = self.0.rss;
+
+        let mut temp = None;
+        let workspace = match workspace {
+            Some(ws) => {
+                assert!(ws.len() >= size);
+                ws.as_mut_ptr()
+            }
+            None if size == 0 => null_mut(),
+            None => temp.insert(self.malloc_workspace()).as_mut_ptr(),
+        };
+        mcfft!(mcfftSetWorkArea(handle, workspace.cast()));
+        mcfft!(mcfftSetStream(handle, stream.as_raw() as _));
+
+        let real_direction = match shape.ty() {
+            R2C | D2Z => Some(Direction::Forward),
+            C2R | Z2D => Some(Direction::Inverse),
+            C2C | Z2Z => None,
+        };
+        assert!(real_direction.is_none_or(|d| d == direction));
+        let sign = match direction {
+            Direction::Forward => FORWARD,
+            Direction::Inverse => INVERSE,
+        };
+        match shape.ty() {
+            R2C => mcfft!(mcfftExecR2C(handle, input.cast(), output.cast())),
+            C2R => mcfft!(mcfftExecC2R(handle, input.cast(), output.cast())),
+            C2C => mcfft!(mcfftExecC2C(handle, input.cast(), output.cast(), sign)),
+            D2Z => mcfft!(mcfftExecD2Z(handle, input.cast(), output.cast())),
+            Z2D => mcfft!(mcfftExecZ2D(handle, input.cast(), output.cast())),
+            Z2Z => mcfft!(mcfftExecZ2Z(handle, input.cast(), output.cast(), sign)),
+        }
+        if let Some(temp) = temp {
+            temp.drop_on(stream);
+        }
+    }
+}
+
+#[test]
+fn test_fft() {
+    use runtime::{memcpy_d2h, memcpy_h2d, Device, NoDevice};
+
+    if let Err(NoDevice) = runtime::init() {
+        return;
+    }
+    Device::new(0).context().apply(|ctx| {
+        let stream = ctx.stream();
+        let plan = Plan::new(ctx, FftShape::new(&[8], FftType::R2C, 1));
+        let mut workspace = plan.malloc_workspace();
+
+        // 常数信号只有直流分量
+        let mut input = ctx.malloc::<f32>(8);
+        let mut output = ctx.malloc::<[f32; 2]>(5);
+        memcpy_h2d(&mut input, &[1.0f32; 8]);
+        plan.exec(
+            &stream,
+            &mut input,
+            &mut output,
+            Direction::Forward,
+            Some(&mut workspace),
+        );
+        stream.synchronize();
+
+        let mut host = [[0.0f32; 2]; 5];
+        memcpy_d2h(&mut host, &output);
+        assert_eq!(host[0], [8., 0.]);
+        assert!(host[1..].iter().flatten().all(|x| x.abs() < 1e-5));
+    });
+}
@@ -0,0 +1 @@
+#include <mcfft/mcfft.h>
@@ -0,0 +1,229 @@
+use crate::bindings::{mcrandGenerator_t, mcrandRngType_t, mcrandStatus_t};
+use crate::DevSliceMut;
+use context_spore::{impl_spore, AsRaw};
+use runtime::{CurrentCtx, Stream};
+use std::{marker::PhantomData, ptr::null_mut};
+
+impl_spore!(Generator and GeneratorSpore by (CurrentCtx, mcrandGenerator_t));
+
+/// 伪随机数算法。
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub enum RngType {
+    #[default]
+    Default,
+    Xorwow,
+    Mrg32k3a,
+    Philox4x32_10,
+}
+
+impl From<RngType> for mcrandRngType_t {
+    #[inline]
+    fn from(ty: RngType) -> Self {
+        match ty {
+            RngType::Default => Self::MCRAND_RNG_PSEUDO_DEFAULT,
+            RngType::Xorwow => Self::MCRAND_RNG_PSEUDO_XORWOW,
+            RngType::Mrg32k3a => Self::MCRAND_RNG_PSEUDO_MRG32K3A,
+            RngType::Philox4x32_10 => Self::MCRAND_RNG_PSEUDO_PHILOX4_32_10,
+        }
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// 可以生成的浮点类型，只由本库实现。
+pub trait RandFloat: sealed::Sealed + Copy + 'static {
+    /// # Safety
+    ///
+    /// `ptr` must point to `n` elements of device memory.
+    unsafe fn uniform(gen: mcrandGenerator_t, ptr: *mut Self, n: usize) -> mcrandStatus_t;
+    /// # Safety
+    ///
+    /// `ptr` must point to `n` elements of device memory.
+    unsafe fn normal(
+        gen: mcrandGenerator_t,
+        ptr: *mut Self,
+        n: usize,
+        mean: Self,
+        stddev: Self,
+    ) -> mcrandStatus_t;
+    /// # Safety
+    ///
+    /// `ptr` must point to `n` elements of device memory.
+    unsafe fn log_normal(
+        gen: mcrandGenerator_t,
+        ptr: *mut Self,
+        n: usize,
+        mean: Self,
+        stddev: Self,
+    ) -> mcrandStatus_t;
+}
+
+macro_rules! impl_rand_float {
+    ($ty:ty: $uniform:ident, $normal:ident, $log_normal:ident) => {
+        impl RandFloat for $ty {
+            #[inline]
+            unsafe fn uniform(gen: mcrandGenerator_t, ptr: *mut Self, n: usize) -> mcrandStatus_t {
+                crate::bindings::$uniform(gen, ptr, n)
+            }
+            #[inline]
+            unsafe fn normal(
+                gen: mcrandGenerator_t,
+                ptr: *mut Self,
+                n: usize,
+                mean: Self,
+                stddev: Self,
+            ) -> mcrandStatus_t {
+                crate::bindings::$normal(gen, ptr, n, mean, stddev)
+            }
+            #[inline]
+            unsafe fn log_normal(
+                gen: mcrandGenerator_t,
+                ptr: *mut Self,
+                n: usize,
+                mean: Self,
+                stddev: Self,
+            ) -> mcrandStatus_t {
+                crate::bindings::$log_normal(gen, ptr, n, mean, stddev)
+            }
+        }
+    };
+}
+
+impl_rand_float!(f32: mcrandGenerateUniform, mcrandGenerateNormal, mcrandGenerateLogNormal);
+impl_rand_float!(f64: mcrandGenerateUniformDouble, mcrandGenerateNormalDouble, mcrandGenerateLogNormalDouble);
+
+impl<'ctx> Generator<'ctx> {
+    pub fn new(ctx: &'ctx CurrentCtx, ty: RngType, seed: u64) -> Self {
+        let mut gen = null_mut();
+        mcrand!(mcrandCreateGenerator(&mut gen, ty.into()));
+        mcrand!(mcrandSetPseudoRandomGeneratorSeed(gen, seed));
+        Self(unsafe { ctx.wrap_raw(gen) }, PhantomData)
+    }
+
+    /// 相同种子和偏移总是产生相同序列的生成器，与设备型号无关，用于复现测试结果。
+    pub fn deterministic(ctx: &'ctx CurrentCtx, seed: u64) -> Self {
+        use crate::bindings::mcrandOrdering_t::MCRAND_ORDERING_PSEUDO_LEGACY;
+        let gen = Self::new(ctx, RngType::Philox4x32_10, seed);
+        mcrand!(mcrandSetGeneratorOrdering(
+            gen.0.rss,
+            MCRAND_ORDERING_PSEUDO_LEGACY
+        ));
+        gen
+    }
+}
+
+impl Drop for Generator<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        mcrand!(mcrandDestroyGenerator(self.0.rss));
+    }
+}
+
+impl AsRaw for Generator<'_> {
+    type Raw = mcrandGenerator_t;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0.rss
+    }
+}
+
+impl Generator<'_> {
+    /// 重设种子，同时把偏移归零。
+    #[inline]
+    pub fn set_seed(&mut self, seed: u64) {
+        mcrand!(mcrandSetPseudoRandomGeneratorSeed(self.0.rss, seed));
+        self.set_offset(0);
+    }
+
+    /// 跳过序列中的前 `offset` 个数。
+    #[inline]
+    pub fn set_offset(&mut self, offset: u64) {
+        mcrand!(mcrandSetGeneratorOffset(self.0.rss, offset));
+    }
+
+    /// 以 `(0, 1]` 上的均匀分布填充 `dst`。
+    pub fn uniform<T: RandFloat>(&mut self, stream: &Stream, dst: &mut DevSliceMut<T>) {
+        self.bind(stream);
+        mcrand!(T::uniform(self.0.rss, dst.as_mut_ptr(), dst.len()));
+    }
+
+    /// 以正态分布填充 `dst`，元素数须为偶数。
+    pub fn normal<T: RandFloat>(
+        &mut self,
+        stream: &Stream,
+        dst: &mut DevSliceMut<T>,
+        mean: T,
+        stddev: T,
+    ) {
+        assert_eq!(dst.len() % 2, 0);
+        self.bind(stream);
+        mcrand!(T::normal(
+            self.0.rss,
+            dst.as_mut_ptr(),
+            dst.len(),
+            mean,
+            stddev
+        ));
+    }
+
+    /// 以对数正态分布填充 `dst`，`mean` 和 `stddev` 是对应正态分布的参数，元素数须为偶数。
+    pub fn log_normal<T: RandFloat>(
+        &mut self,
+        stream: &Stream,
+        dst: &mut DevSliceMut<T>,
+        mean: T,
+        stddev: T,
+    ) {
+        assert_eq!(dst.len() % 2, 0);
+        self.bind(stream);
+        mcrand!(T::log_normal(
+            self.0.rss,
+            dst.as_mut_ptr(),
+            dst.len(),
+            mean,
+            stddev
+        ));
+    }
+
+    /// 以均匀分布的 32 位整数填充 `dst`。
+    pub fn bits(&mut self, stream: &Stream, dst: &mut DevSliceMut<u32>) {
+        self.bind(stream);
+        mcrand!(mcrandGenerate(self.0.rss, dst.as_mut_ptr(), dst.len()));
+    }
+
+    #[inline]
+    fn bind(&self, stream: &Stream) {
+        mcrand!(mcrandSetStream(self.0.rss, stream.as_raw() as _));
+    }
+}
+
+#[test]
+fn test_deterministic() {
+    use runtime::{memcpy_d2h, Device, NoDevice};
+
+    if let Err(NoDevice) = runtime::init() {
+        return;
+    }
+    Device::new(0).context().apply(|ctx| {
+        let stream = ctx.stream();
+        let samples = || {
+            let mut gen = Generator::deterministic(ctx, 42);
+            let mut dev = ctx.malloc::<f32>(256);
+            let mut dst = DevSliceMut::new(&mut dev).unwrap();
+            gen.normal(&stream, &mut dst, 0.0f32, 1.0);
+            stream.synchronize();
+            let mut host = vec![0.0f32; 256];
+            memcpy_d2h(&mut host, &dev);
+            host
+        };
+        let a = samples();
+        assert_eq!(a, samples());
+
+        let mean = a.iter().sum::<f32>() / a.len() as f32;
+        assert!(mean.abs() < 0.3);
+    });
+}
@@ -0,0 +1,40 @@
+#![cfg(detected_mx)]
+
+#[macro_use]
+#[allow(
+    unused,
+    non_upper_case_globals,
+    non_camel_case_types,
+    non_snake_case,
+    clippy::useless_transmute,
+    clippy::too_many_arguments
+)]
+pub mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+    #[macro_export]
+    macro_rules! mcrand {
+        ($f:expr) => {{
+            #[allow(unused_imports)]
+            use $crate::bindings::*;
+            #[allow(unused_unsafe, clippy::macro_metavars_in_unsafe)]
+            let status = unsafe { $f };
+            assert_eq!(status, mcrandStatus_t::MCRAND_STATUS_SUCCESS);
+        }};
+    }
+}
+
+mod generator;
+mod slice;
+
+pub use generator::{Generator, GeneratorSpore, RandFloat, RngType};
+pub use slice::DevSliceMut;
+
+/// 设备内存不能按请求的类型解释。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RandError {
+    /// 长度不是元素大小的整数倍，以字节计。
+    Length { len: usize, elem: usize },
+    /// 首地址没有按元素类型对齐。
+    Misaligned,
+}
@@ -0,0 +1,66 @@
+use crate::RandError;
+use runtime::DevByte;
+use std::{marker::PhantomData, mem::size_of};
+
+/// 可写的类型化设备切片。
+pub struct DevSliceMut<'a, T> {
+    data: &'a mut [DevByte],
+    _t: PhantomData<T>,
+}
+
+impl<'a, T: Copy> DevSliceMut<'a, T> {
+    /// 把 `data` 解释为连续存储的 `T`。
+    pub fn new(data: &'a mut [DevByte]) -> Result<Self, RandError> {
+        let elem = size_of::<T>();
+        if !data.len().is_multiple_of(elem) {
+            return Err(RandError::Length {
+                len: data.len(),
+                elem,
+            });
+        }
+        if !data.as_ptr().cast::<T>().is_aligned() {
+            return Err(RandError::Misaligned);
+        }
+        Ok(Self {
+            data,
+            _t: PhantomData,
+        })
+    }
+
+    /// 元素数。
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len() / size_of::<T>()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr().cast()
+    }
+}
+
+#[test]
+fn test_new() {
+    use runtime::{Device, NoDevice};
+
+    if let Err(NoDevice) = runtime::init() {
+        return;
+    }
+    Device::new(0).context().apply(|ctx| {
+        let mut dev = ctx.malloc::<u8>(10);
+        assert_eq!(
+            DevSliceMut::<f32>::new(&mut dev).err(),
+            Some(RandError::Length { len: 10, elem: 4 })
+        );
+        assert_eq!(
+            DevSliceMut::<f32>::new(&mut dev[1..9]).err(),
+            Some(RandError::Misaligned)
+        );
+        assert_eq!(DevSliceMut::<f32>::new(&mut dev[..8]).unwrap().len(), 2);
+    });
+}
@@ -0,0 +1 @@
+#include <mcrand/mcrand.h>
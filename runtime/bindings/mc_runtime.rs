@@ -0,0 +1,559 @@
+// Hand-written bindings of the MACA runtime API used by this crate, not produced by
+// `regen-bindings` and not checked against any toolkit release.
+// Enums are newtypes so that codes missing here are still valid values;
+// struct layouts follow the CUDA runtime and are unverified.
+// Replace with the output of
+// `regen-bindings --newtype-enums wrapper.h 'mc.*' mcError_t bindings/mc_runtime.rs --write`.
+
+pub type mcDevice_t = ::core::ffi::c_int;
+pub const mcDeviceScheduleAuto: u32 = 0;
+pub const mcDeviceScheduleSpin: u32 = 1;
+pub const mcDeviceScheduleYield: u32 = 2;
+pub const mcDeviceScheduleBlockingSync: u32 = 4;
+pub const mcDeviceScheduleMask: u32 = 7;
+pub const mcDeviceMapHost: u32 = 8;
+pub const mcDeviceLmemResizeToMax: u32 = 16;
+#[repr(C)]
+pub struct MCctx_st {
+    _unused: [u8; 0],
+}
+pub type MCcontext = *mut MCctx_st;
+pub type mcCtx_t = MCcontext;
+#[repr(C)]
+pub struct mcStream_st {
+    _unused: [u8; 0],
+}
+pub type mcStream_t = *mut mcStream_st;
+#[repr(C)]
+pub struct mcEvent_st {
+    _unused: [u8; 0],
+}
+pub type mcEvent_t = *mut mcEvent_st;
+pub type mcDeviceptr_t = *mut ::core::ffi::c_void;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct mcUUID_t {
+    pub bytes: [::core::ffi::c_char; 16usize],
+}
+pub type mcUUID = mcUUID_t;
+impl mcError_t {
+    pub const mcSuccess: mcError_t = mcError_t(0);
+    pub const mcErrorInvalidValue: mcError_t = mcError_t(1);
+    pub const mcErrorMemoryAllocation: mcError_t = mcError_t(2);
+    pub const mcErrorInitializationError: mcError_t = mcError_t(3);
+    pub const mcErrorInvalidDevice: mcError_t = mcError_t(101);
+    pub const mcErrorNotReady: mcError_t = mcError_t(600);
+    pub const mcErrorNotSupported: mcError_t = mcError_t(801);
+    pub const mcErrorGraphExecUpdateFailure: mcError_t = mcError_t(910);
+}
+#[repr(transparent)]
+#[must_use]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcError_t(pub ::core::ffi::c_uint);
+#[repr(C)]
+pub struct mcGraph_st {
+    _unused: [u8; 0],
+}
+pub type mcGraph_t = *mut mcGraph_st;
+#[repr(C)]
+pub struct mcGraphExec_st {
+    _unused: [u8; 0],
+}
+pub type mcGraphExec_t = *mut mcGraphExec_st;
+#[repr(C)]
+pub struct mcGraphNode_st {
+    _unused: [u8; 0],
+}
+pub type mcGraphNode_t = *mut mcGraphNode_st;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct dim3 {
+    pub x: ::core::ffi::c_uint,
+    pub y: ::core::ffi::c_uint,
+    pub z: ::core::ffi::c_uint,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct mcKernelNodeParams {
+    pub func: *mut ::core::ffi::c_void,
+    pub gridDim: dim3,
+    pub blockDim: dim3,
+    pub sharedMemBytes: ::core::ffi::c_uint,
+    pub kernelParams: *mut *mut ::core::ffi::c_void,
+    pub extra: *mut *mut ::core::ffi::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct mcMemsetParams {
+    pub dst: *mut ::core::ffi::c_void,
+    pub pitch: usize,
+    pub value: ::core::ffi::c_uint,
+    pub elementSize: ::core::ffi::c_uint,
+    pub width: usize,
+    pub height: usize,
+}
+pub type mcHostFn_t = Option<unsafe extern "C" fn(userData: *mut ::core::ffi::c_void)>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct mcHostNodeParams {
+    pub fn_: mcHostFn_t,
+    pub userData: *mut ::core::ffi::c_void,
+}
+impl mcMemcpyKind {
+    pub const mcMemcpyHostToHost: mcMemcpyKind = mcMemcpyKind(0);
+    pub const mcMemcpyHostToDevice: mcMemcpyKind = mcMemcpyKind(1);
+    pub const mcMemcpyDeviceToHost: mcMemcpyKind = mcMemcpyKind(2);
+    pub const mcMemcpyDeviceToDevice: mcMemcpyKind = mcMemcpyKind(3);
+    pub const mcMemcpyDefault: mcMemcpyKind = mcMemcpyKind(4);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcMemcpyKind(pub ::core::ffi::c_uint);
+impl mcLimit_t {
+    pub const mcLimitStackSize: mcLimit_t = mcLimit_t(0);
+    pub const mcLimitPrintfFifoSize: mcLimit_t = mcLimit_t(1);
+    pub const mcLimitMallocHeapSize: mcLimit_t = mcLimit_t(2);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcLimit_t(pub ::core::ffi::c_uint);
+impl mcFuncCache_t {
+    pub const mcFuncCachePreferNone: mcFuncCache_t = mcFuncCache_t(0);
+    pub const mcFuncCachePreferShared: mcFuncCache_t = mcFuncCache_t(1);
+    pub const mcFuncCachePreferL1: mcFuncCache_t = mcFuncCache_t(2);
+    pub const mcFuncCachePreferEqual: mcFuncCache_t = mcFuncCache_t(3);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcFuncCache_t(pub ::core::ffi::c_uint);
+impl mcSharedMemConfig {
+    pub const mcSharedMemBankSizeDefault: mcSharedMemConfig = mcSharedMemConfig(0);
+    pub const mcSharedMemBankSizeFourByte: mcSharedMemConfig = mcSharedMemConfig(1);
+    pub const mcSharedMemBankSizeEightByte: mcSharedMemConfig = mcSharedMemConfig(2);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcSharedMemConfig(pub ::core::ffi::c_uint);
+impl mcStreamCaptureMode {
+    pub const mcStreamCaptureModeGlobal: mcStreamCaptureMode = mcStreamCaptureMode(0);
+    pub const mcStreamCaptureModeThreadLocal: mcStreamCaptureMode = mcStreamCaptureMode(1);
+    pub const mcStreamCaptureModeRelaxed: mcStreamCaptureMode = mcStreamCaptureMode(2);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcStreamCaptureMode(pub ::core::ffi::c_uint);
+impl mcStreamCaptureStatus {
+    pub const mcStreamCaptureStatusNone: mcStreamCaptureStatus = mcStreamCaptureStatus(0);
+    pub const mcStreamCaptureStatusActive: mcStreamCaptureStatus = mcStreamCaptureStatus(1);
+    pub const mcStreamCaptureStatusInvalidated: mcStreamCaptureStatus = mcStreamCaptureStatus(2);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcStreamCaptureStatus(pub ::core::ffi::c_uint);
+impl mcGraphExecUpdateResult {
+    pub const mcGraphExecUpdateSuccess: mcGraphExecUpdateResult = mcGraphExecUpdateResult(0);
+    pub const mcGraphExecUpdateError: mcGraphExecUpdateResult = mcGraphExecUpdateResult(1);
+    pub const mcGraphExecUpdateErrorTopologyChanged: mcGraphExecUpdateResult =
+        mcGraphExecUpdateResult(2);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcGraphExecUpdateResult(pub ::core::ffi::c_uint);
+impl mcDeviceAttribute_t {
+    pub const mcDeviceAttributeMaxThreadsPerBlock: mcDeviceAttribute_t = mcDeviceAttribute_t(1);
+    pub const mcDeviceAttributeMaxBlockDimX: mcDeviceAttribute_t = mcDeviceAttribute_t(2);
+    pub const mcDeviceAttributeMaxBlockDimY: mcDeviceAttribute_t = mcDeviceAttribute_t(3);
+    pub const mcDeviceAttributeMaxBlockDimZ: mcDeviceAttribute_t = mcDeviceAttribute_t(4);
+    pub const mcDeviceAttributeMaxGridDimX: mcDeviceAttribute_t = mcDeviceAttribute_t(5);
+    pub const mcDeviceAttributeMaxGridDimY: mcDeviceAttribute_t = mcDeviceAttribute_t(6);
+    pub const mcDeviceAttributeMaxGridDimZ: mcDeviceAttribute_t = mcDeviceAttribute_t(7);
+    pub const mcDeviceAttributeMaxSharedMemoryPerBlock: mcDeviceAttribute_t =
+        mcDeviceAttribute_t(8);
+    pub const mcDeviceAttributeTotalConstantMemory: mcDeviceAttribute_t = mcDeviceAttribute_t(9);
+    pub const mcDeviceAttributeWarpSize: mcDeviceAttribute_t = mcDeviceAttribute_t(10);
+    pub const mcDeviceAttributeMaxRegistersPerBlock: mcDeviceAttribute_t = mcDeviceAttribute_t(12);
+    pub const mcDeviceAttributeClockRate: mcDeviceAttribute_t = mcDeviceAttribute_t(13);
+    pub const mcDeviceAttributeTextureAlignment: mcDeviceAttribute_t = mcDeviceAttribute_t(14);
+    pub const mcDeviceAttributeMultiProcessorCount: mcDeviceAttribute_t = mcDeviceAttribute_t(16);
+    pub const mcDeviceAttributeL2CacheSize: mcDeviceAttribute_t = mcDeviceAttribute_t(38);
+    pub const mcDeviceAttributeComputeCapabilityMajor: mcDeviceAttribute_t =
+        mcDeviceAttribute_t(75);
+    pub const mcDeviceAttributeComputeCapabilityMinor: mcDeviceAttribute_t =
+        mcDeviceAttribute_t(76);
+    pub const mcDeviceAttributeMaxSharedMemoryPerMultiprocessor: mcDeviceAttribute_t =
+        mcDeviceAttribute_t(81);
+    pub const mcDeviceAttributeMaxRegistersPerMultiprocessor: mcDeviceAttribute_t =
+        mcDeviceAttribute_t(82);
+    pub const mcDeviceAttributeMaxThreadsPerMultiProcessor: mcDeviceAttribute_t =
+        mcDeviceAttribute_t(39);
+    pub const mcDevAttrMaxBlocksPerMultiprocessor: mcDeviceAttribute_t = mcDeviceAttribute_t(106);
+    pub const mcDeviceAttributeMemoryClockRate: mcDeviceAttribute_t = mcDeviceAttribute_t(36);
+    pub const mcDeviceAttributeMemoryBusWidth: mcDeviceAttribute_t = mcDeviceAttribute_t(37);
+    pub const mcDeviceAttributeConcurrentKernels: mcDeviceAttribute_t = mcDeviceAttribute_t(31);
+    pub const mcDeviceAttributeEccEnabled: mcDeviceAttribute_t = mcDeviceAttribute_t(32);
+    pub const mcDeviceAttributeAsyncEngineCount: mcDeviceAttribute_t = mcDeviceAttribute_t(40);
+    pub const mcDeviceAttributeUnifiedAddressing: mcDeviceAttribute_t = mcDeviceAttribute_t(41);
+    pub const mcDeviceAttributeManagedMemory: mcDeviceAttribute_t = mcDeviceAttribute_t(83);
+    pub const mcDeviceAttributeConcurrentManagedAccess: mcDeviceAttribute_t =
+        mcDeviceAttribute_t(89);
+    pub const mcDeviceAttributeComputeMode: mcDeviceAttribute_t = mcDeviceAttribute_t(20);
+    pub const mcDeviceAttributeIntegrated: mcDeviceAttribute_t = mcDeviceAttribute_t(18);
+    pub const mcDeviceAttributeCanMapHostMemory: mcDeviceAttribute_t = mcDeviceAttribute_t(19);
+    pub const mcDeviceAttributeCooperativeLaunch: mcDeviceAttribute_t = mcDeviceAttribute_t(95);
+    pub const mcDeviceAttributeKernelExecTimeout: mcDeviceAttribute_t = mcDeviceAttribute_t(17);
+    pub const mcDeviceAttributePciBusId: mcDeviceAttribute_t = mcDeviceAttribute_t(33);
+    pub const mcDeviceAttributePciDeviceId: mcDeviceAttribute_t = mcDeviceAttribute_t(34);
+    pub const mcDeviceAttributePciDomainID: mcDeviceAttribute_t = mcDeviceAttribute_t(50);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct mcDeviceAttribute_t(pub ::core::ffi::c_uint);
+extern "C" {
+    pub fn mcInit(flags: ::core::ffi::c_uint) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGetDeviceCount(count: *mut ::core::ffi::c_int) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDeviceGet(device: *mut mcDevice_t, ordinal: ::core::ffi::c_int) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDeviceGetName(
+        name: *mut ::core::ffi::c_char,
+        len: ::core::ffi::c_int,
+        device: mcDevice_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDeviceTotalMem(bytes: *mut usize, device: mcDevice_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDeviceGetAttribute(
+        pi: *mut ::core::ffi::c_int,
+        attr: mcDeviceAttribute_t,
+        device: ::core::ffi::c_int,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxCreate(
+        ctx: *mut mcCtx_t,
+        flags: ::core::ffi::c_uint,
+        device: mcDevice_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxDestroy(ctx: mcCtx_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxPopCurrent(ctx: *mut mcCtx_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxPushCurrent(ctx: mcCtx_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxGetCurrent(ctx: *mut mcCtx_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxGetDevice(device: *mut mcDevice_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxSynchronize() -> mcError_t;
+}
+extern "C" {
+    pub fn mcDevicePrimaryCtxRetain(pctx: *mut mcCtx_t, dev: mcDevice_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDevicePrimaryCtxReset(dev: mcDevice_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDevicePrimaryCtxGetState(
+        dev: mcDevice_t,
+        flags: *mut ::core::ffi::c_uint,
+        active: *mut ::core::ffi::c_int,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcHostRegister(
+        ptr: *mut ::core::ffi::c_void,
+        size: usize,
+        flags: ::core::ffi::c_uint,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcHostUnregister(ptr: *mut ::core::ffi::c_void) -> mcError_t;
+}
+extern "C" {
+    pub fn mcEventCreate(event: *mut mcEvent_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcEventDestroy(event: mcEvent_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcEventSynchronize(event: mcEvent_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcEventElapsedTime(ms: *mut f32, start: mcEvent_t, end: mcEvent_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamWaitEvent(
+        stream: mcStream_t,
+        event: mcEvent_t,
+        flags: ::core::ffi::c_uint,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamCreate(stream: *mut mcStream_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamDestroy(stream: mcStream_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamSynchronize(stream: mcStream_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMemcpyDtoH(
+        dst: *mut ::core::ffi::c_void,
+        src: mcDeviceptr_t,
+        size: usize,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMemcpyHtoD(
+        dst: mcDeviceptr_t,
+        src: *const ::core::ffi::c_void,
+        size: usize,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMemcpyDtoD(dst: mcDeviceptr_t, src: mcDeviceptr_t, size: usize) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMemcpyHtoDAsync(
+        dst: mcDeviceptr_t,
+        src: *const ::core::ffi::c_void,
+        size: usize,
+        stream: mcStream_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMemcpyDtoDAsync(
+        dst: mcDeviceptr_t,
+        src: mcDeviceptr_t,
+        size: usize,
+        stream: mcStream_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMalloc(ptr: *mut *mut ::core::ffi::c_void, size: usize) -> mcError_t;
+}
+extern "C" {
+    pub fn mcFree(ptr: *mut ::core::ffi::c_void) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMemFreeAsync(ptr: mcDeviceptr_t, stream: mcStream_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMallocHost(
+        ptr: *mut *mut ::core::ffi::c_void,
+        size: usize,
+        flags: ::core::ffi::c_uint,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcEventRecord(event: mcEvent_t, stream: mcStream_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMemsetAsync(
+        ptr: *mut ::core::ffi::c_void,
+        value: ::core::ffi::c_int,
+        size: usize,
+        stream: mcStream_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamBeginCapture(stream: mcStream_t, mode: mcStreamCaptureMode) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamEndCapture(stream: mcStream_t, graph: *mut mcGraph_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamIsCapturing(stream: mcStream_t, status: *mut mcStreamCaptureStatus)
+        -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphCreate(graph: *mut mcGraph_t, flags: ::core::ffi::c_uint) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphDestroy(graph: mcGraph_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphInstantiateWithFlags(
+        exec: *mut mcGraphExec_t,
+        graph: mcGraph_t,
+        flags: u64,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphExecDestroy(exec: mcGraphExec_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphLaunch(exec: mcGraphExec_t, stream: mcStream_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphExecUpdate(
+        exec: mcGraphExec_t,
+        graph: mcGraph_t,
+        error_node: *mut mcGraphNode_t,
+        result: *mut mcGraphExecUpdateResult,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddEmptyNode(
+        node: *mut mcGraphNode_t,
+        graph: mcGraph_t,
+        deps: *const mcGraphNode_t,
+        n: usize,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddKernelNode(
+        node: *mut mcGraphNode_t,
+        graph: mcGraph_t,
+        deps: *const mcGraphNode_t,
+        n: usize,
+        params: *const mcKernelNodeParams,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddMemcpyNode1D(
+        node: *mut mcGraphNode_t,
+        graph: mcGraph_t,
+        deps: *const mcGraphNode_t,
+        n: usize,
+        dst: *mut ::core::ffi::c_void,
+        src: *const ::core::ffi::c_void,
+        count: usize,
+        kind: mcMemcpyKind,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddMemsetNode(
+        node: *mut mcGraphNode_t,
+        graph: mcGraph_t,
+        deps: *const mcGraphNode_t,
+        n: usize,
+        params: *const mcMemsetParams,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddHostNode(
+        node: *mut mcGraphNode_t,
+        graph: mcGraph_t,
+        deps: *const mcGraphNode_t,
+        n: usize,
+        params: *const mcHostNodeParams,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddEventRecordNode(
+        node: *mut mcGraphNode_t,
+        graph: mcGraph_t,
+        deps: *const mcGraphNode_t,
+        n: usize,
+        event: mcEvent_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddEventWaitNode(
+        node: *mut mcGraphNode_t,
+        graph: mcGraph_t,
+        deps: *const mcGraphNode_t,
+        n: usize,
+        event: mcEvent_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddChildGraphNode(
+        node: *mut mcGraphNode_t,
+        graph: mcGraph_t,
+        deps: *const mcGraphNode_t,
+        n: usize,
+        child: mcGraph_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcGraphAddDependencies(
+        graph: mcGraph_t,
+        from: *const mcGraphNode_t,
+        to: *const mcGraphNode_t,
+        n: usize,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamCreateWithPriority(
+        stream: *mut mcStream_t,
+        flags: ::core::ffi::c_uint,
+        priority: ::core::ffi::c_int,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcStreamGetPriority(stream: mcStream_t, priority: *mut ::core::ffi::c_int) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDeviceGetStreamPriorityRange(
+        least: *mut ::core::ffi::c_int,
+        greatest: *mut ::core::ffi::c_int,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDeviceGetUuid(uuid: *mut mcUUID, device: mcDevice_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDeviceGetPCIBusId(
+        bus_id: *mut ::core::ffi::c_char,
+        len: ::core::ffi::c_int,
+        device: mcDevice_t,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDeviceGetByPCIBusId(
+        device: *mut ::core::ffi::c_int,
+        bus_id: *const ::core::ffi::c_char,
+    ) -> mcError_t;
+}
+extern "C" {
+    pub fn mcMemGetInfo(free: *mut usize, total: *mut usize) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxGetFlags(flags: *mut ::core::ffi::c_uint) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDevicePrimaryCtxSetFlags(dev: mcDevice_t, flags: ::core::ffi::c_uint) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxSetLimit(limit: mcLimit_t, value: usize) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxGetLimit(value: *mut usize, limit: mcLimit_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxSetCacheConfig(config: mcFuncCache_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxGetCacheConfig(config: *mut mcFuncCache_t) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxSetSharedMemConfig(config: mcSharedMemConfig) -> mcError_t;
+}
+extern "C" {
+    pub fn mcCtxGetSharedMemConfig(config: *mut mcSharedMemConfig) -> mcError_t;
+}
+extern "C" {
+    pub fn mcDriverGetVersion(v: *mut ::core::ffi::c_int) -> mcError_t;
+}
+extern "C" {
+    pub fn mcRuntimeGetVersion(v: *mut ::core::ffi::c_int) -> mcError_t;
+}
+extern "C" {
+    pub fn mcFreeHost(ptr: *mut ::core::ffi::c_void) -> mcError_t;
+}
@@ -1,40 +1,88 @@
-use std::{env, path::PathBuf};
+use std::{
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+/// 固定工具链版本预生成的绑定，在无法运行 bindgen 时使用。
+const PREGENERATED: &str = "bindings/mc_runtime.rs";
 
 fn main() {
     use build_script_cfg::Cfg;
-    use search_mx_tools::find_mx_home;
+    use search_mx_tools::MxToolkit;
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={PREGENERATED}");
+    println!("cargo:rerun-if-env-changed=DOCS_RS");
 
-    println!("cargo:rereun-if-changed=build.rs");
+    let mx_cfg = Cfg::new("detected_mx");
+    let out_path = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("bindings.rs");
+    // docs.rs 上没有工具链，也不需要链接
+    let force = env::var_os("CARGO_FEATURE_PREGENERATED_BINDINGS").is_some()
+        || env::var_os("DOCS_RS").is_some();
 
-    let mx = Cfg::new("detected_mx");
-    let Some(mx_home) = find_mx_home() else {
+    MxToolkit::emit_rerun_if_env_changed();
+    let mx = MxToolkit::find();
+    if mx.is_none() && !force {
         return;
-    };
-    mx.define();
-    println!("{}", mx_home.join("lib").display());
-    println!(
-        "cargo:rustc-link-search=native={}",
-        mx_home.join("lib").display()
-    );
+    }
+
+    if let Some(mx) = &mx {
+        // 工具链升级后需要重新记录版本
+        if let Some(version) = mx.emit_version_cfg(&[]) {
+            println!(
+                "cargo:rustc-env=MACA_BUILD_VERSION={}.{}",
+                version.major, version.minor
+            );
+        }
+        println!("cargo:rustc-link-search=native={}", mx.lib.display());
+    }
+    // 没有找到工具链时依赖系统的库搜索路径
     println!("cargo:rustc-link-lib=dylib=mcruntime");
     println!("cargo:rustc-link-lib=dylib=mxc-runtime64");
 
-    println!("cargo-rerun-if-changed=wrapper.h");
-
-    let bindings = bindgen::Builder::default()
-        .header("wrapper.h")
-        .clang_arg(format!("-I{}", mx_home.join("include").display()))
-        .allowlist_item("mc.*")
-        .must_use_type("mcError_t")
-        .default_enum_style(bindgen::EnumVariation::Rust {
-            non_exhaustive: true,
-        })
-        .use_core()
-        .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
-        .generate()
-        .expect("Unable to generate bindings");
-    let out_path = PathBuf::from(env::var_os("OUT_DIR").unwrap());
-    bindings
-        .write_to_file(out_path.join("bindings.rs"))
-        .expect("Couldn't write bindings!");
+    let generated = match &mx {
+        Some(mx) if !force => generate(mx, &out_path),
+        _ => false,
+    };
+    if !generated {
+        fs::copy(PREGENERATED, &out_path).expect("Couldn't copy pregenerated bindings!");
+    }
+    mx_cfg.define();
+}
+
+/// 运行 bindgen 生成绑定，头文件或 libclang 缺失时返回 `false`。
+fn generate(mx: &search_mx_tools::MxToolkit, out_path: &Path) -> bool {
+    use search_mx_tools::bindgen::EnumVariation;
+
+    // 找不到 libclang 时 bindgen 会 panic，暂时屏蔽默认的 panic 输出
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        // 与预生成的绑定一致，枚举生成为整数的新类型，驱动返回未列出的值时不会产生未定义行为
+        mx.bindgen("wrapper.h", "mc.*", "mcError_t")
+            .default_enum_style(EnumVariation::NewType {
+                is_bitfield: false,
+                is_global: false,
+            })
+            .generate()
+    }));
+    panic::set_hook(hook);
+
+    match result {
+        Ok(Ok(bindings)) => {
+            bindings
+                .write_to_file(out_path)
+                .expect("Couldn't write bindings!");
+            true
+        }
+        Ok(Err(e)) => {
+            println!("cargo:warning=bindgen failed ({e}), using {PREGENERATED}");
+            false
+        }
+        Err(_) => {
+            println!("cargo:warning=libclang not found, using {PREGENERATED}");
+            false
+        }
+    }
 }
@@ -0,0 +1,259 @@
+use crate::{Event, Stream};
+use context_spore::AsRaw;
+use std::{fmt, time::Duration};
+
+/// 基准测试的终止条件。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Budget {
+    /// 固定的测试次数。
+    Times(usize),
+    /// 累计的设备耗时上限，至少执行一次。
+    Time(Duration),
+}
+
+/// 每次迭代完成的工作量，用于计算吞吐。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Work {
+    Bytes(usize),
+    Flops(usize),
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Benchmark {
+    warm_up: usize,
+    budget: Budget,
+    flush_l2: bool,
+}
+
+impl Benchmark {
+    /// 固定测试 `times` 次，`times` 不能为 0。
+    #[inline]
+    pub const fn times(times: usize) -> Self {
+        assert!(times > 0, "Benchmark needs at least one iteration");
+        Self {
+            warm_up: 0,
+            budget: Budget::Times(times),
+            flush_l2: false,
+        }
+    }
+
+    #[inline]
+    pub const fn budget(time: Duration) -> Self {
+        Self {
+            warm_up: 0,
+            budget: Budget::Time(time),
+            flush_l2: false,
+        }
+    }
+
+    #[inline]
+    pub const fn warm_up(mut self, warm_up: usize) -> Self {
+        self.warm_up = warm_up;
+        self
+    }
+
+    /// 在每次迭代之前写一遍大于 L2 的缓冲区，使每次迭代都从冷缓存开始。
+    #[inline]
+    pub const fn flush_l2(mut self, flush: bool) -> Self {
+        self.flush_l2 = flush;
+        self
+    }
+
+    pub fn run(&self, stream: &Stream, mut f: impl FnMut(usize, &Stream)) -> BenchReport {
+        for i in 0..self.warm_up {
+            f(i, stream);
+        }
+
+        let mut flush = if self.flush_l2 {
+            let l2 = stream.ctx().dev().l2_cache_size().0;
+            Some(stream.ctx().malloc::<u8>(l2.max(1) * 2))
+        } else {
+            None
+        };
+
+        let mut events = Vec::<(Event, Event)>::new();
+        let mut samples = Vec::new();
+        let mut total = Duration::ZERO;
+        for i in 0.. {
+            match self.budget {
+                Budget::Times(times) if i >= times => break,
+                Budget::Time(time) if i > 0 && total >= time => break,
+                _ => {}
+            }
+            if let Some(buf) = &mut flush {
+                mxdrv!(mcMemsetAsync(
+                    buf.as_mut_ptr() as _,
+                    0,
+                    buf.len(),
+                    stream.as_raw()
+                ));
+            }
+            let start = stream.record();
+            f(i, stream);
+            let end = stream.record();
+            if let Budget::Time(_) = self.budget {
+                // 按时间预算测试时需要逐次同步以累计耗时
+                end.synchronize();
+                let t = end.elapse_from(&start);
+                total += t;
+                samples.push(t);
+            } else {
+                events.push((start, end));
+            }
+        }
+        if let Some((_, end)) = events.last() {
+            end.synchronize();
+        }
+        samples.extend(events.iter().map(|(start, end)| end.elapse_from(start)));
+        if let Some(buf) = flush {
+            buf.drop_on(stream);
+        }
+        BenchReport::new(samples)
+    }
+}
+
+/// 逐次迭代的测试结果及其统计量。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BenchReport {
+    samples: Vec<Duration>,
+    sorted: Vec<Duration>,
+}
+
+impl BenchReport {
+    pub fn new(samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty());
+        let mut sorted = samples.clone();
+        sorted.sort_unstable();
+        Self { samples, sorted }
+    }
+
+    /// 按迭代顺序排列的耗时。
+    #[inline]
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    #[inline]
+    pub fn min(&self) -> Duration {
+        self.sorted[0]
+    }
+
+    #[inline]
+    pub fn max(&self) -> Duration {
+        self.sorted[self.sorted.len() - 1]
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    #[inline]
+    pub fn median(&self) -> Duration {
+        self.percentile(50.)
+    }
+
+    #[inline]
+    pub fn p90(&self) -> Duration {
+        self.percentile(90.)
+    }
+
+    #[inline]
+    pub fn p99(&self) -> Duration {
+        self.percentile(99.)
+    }
+
+    /// 以线性插值计算 `p` 百分位数，`p` 取值范围为 `[0, 100]`。
+    pub fn percentile(&self, p: f64) -> Duration {
+        assert!((0. ..=100.).contains(&p));
+        let pos = p / 100. * (self.sorted.len() - 1) as f64;
+        let lo = pos.floor() as usize;
+        let hi = pos.ceil() as usize;
+        if lo == hi {
+            return self.sorted[lo];
+        }
+        let lo_ = self.sorted[lo].as_secs_f64();
+        let hi_ = self.sorted[hi].as_secs_f64();
+        Duration::from_secs_f64(lo_ + (hi_ - lo_) * (pos - lo as f64))
+    }
+
+    /// 样本标准差。
+    pub fn stddev(&self) -> Duration {
+        let n = self.samples.len();
+        if n < 2 {
+            return Duration::ZERO;
+        }
+        let mean = self.mean().as_secs_f64();
+        let var = self
+            .samples
+            .iter()
+            .map(|t| (t.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1) as f64;
+        Duration::from_secs_f64(var.sqrt())
+    }
+
+    /// 以 Tukey 法则（超出四分位距 1.5 倍）判定的离群样本。
+    pub fn outliers(&self) -> Vec<Duration> {
+        let q1 = self.percentile(25.).as_secs_f64();
+        let q3 = self.percentile(75.).as_secs_f64();
+        let iqr = q3 - q1;
+        let lo = q1 - 1.5 * iqr;
+        let hi = q3 + 1.5 * iqr;
+        self.sorted
+            .iter()
+            .copied()
+            .filter(|t| !(lo..=hi).contains(&t.as_secs_f64()))
+            .collect()
+    }
+
+    /// 以中位数耗时计算的每秒字节数或浮点运算数。
+    pub fn throughput(&self, work: Work) -> f64 {
+        let n = match work {
+            Work::Bytes(n) | Work::Flops(n) => n,
+        };
+        n as f64 / self.median().as_secs_f64()
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "n = {}, min = {:?}, median = {:?}, p90 = {:?}, p99 = {:?}, max = {:?}, stddev = {:?}, outliers = {}",
+            self.len(),
+            self.min(),
+            self.median(),
+            self.p90(),
+            self.p99(),
+            self.max(),
+            self.stddev(),
+            self.outliers().len(),
+        )
+    }
+}
+
+#[test]
+fn test_report() {
+    let mut samples = (1..=100).map(Duration::from_micros).collect::<Vec<_>>();
+    samples.push(Duration::from_millis(10));
+    let report = BenchReport::new(samples);
+    assert_eq!(report.len(), 101);
+    assert_eq!(report.min(), Duration::from_micros(1));
+    assert_eq!(report.max(), Duration::from_millis(10));
+    assert_eq!(report.median(), Duration::from_micros(51));
+    assert_eq!(report.outliers(), [Duration::from_millis(10)]);
+    assert!(report.p90() < report.p99());
+
+    let throughput = report.throughput(Work::Bytes(51 << 20));
+    assert!((throughput - 1e6 * (51 << 20) as f64 / 51.).abs() / throughput < 1e-6);
+}
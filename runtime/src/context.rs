@@ -4,8 +4,14 @@ use crate::{
 };
 use context_spore::{AsRaw, RawContainer};
 use std::{
-    mem::{align_of, size_of},
+    cell::RefCell,
+    collections::BTreeMap,
+    ffi::c_uint,
+    marker::PhantomData,
+    mem::{align_of, forget, size_of},
+    ops::Deref,
     ptr::null_mut,
+    sync::Mutex,
 };
 
 #[derive(PartialEq, Eq, Hash, Debug)]
@@ -15,15 +21,101 @@ pub struct Context {
     primary: bool,
 }
 
-impl Device {
+/// CPU 等待设备时的调度策略。
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub enum Schedule {
+    /// 由驱动根据活跃上下文数和处理器数决定。
+    #[default]
+    Auto,
+    /// 自旋等待，延迟最低但占满一个 CPU 核。
+    Spin,
+    /// 等待时让出 CPU 线程。
+    Yield,
+    /// 阻塞在同步原语上直到设备完成。
+    BlockingSync,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct ContextFlags {
+    pub schedule: Schedule,
+    /// 允许映射锁页主机存储到设备地址空间。
+    pub map_host: bool,
+    /// 核函数使用的本地存储在调整后不收缩。
+    pub lmem_resize_to_max: bool,
+}
+
+impl ContextFlags {
+    pub fn to_raw(self) -> c_uint {
+        use crate::bindings::*;
+        let schedule = match self.schedule {
+            Schedule::Auto => mcDeviceScheduleAuto,
+            Schedule::Spin => mcDeviceScheduleSpin,
+            Schedule::Yield => mcDeviceScheduleYield,
+            Schedule::BlockingSync => mcDeviceScheduleBlockingSync,
+        };
+        let mut flags = schedule as c_uint;
+        if self.map_host {
+            flags |= mcDeviceMapHost as c_uint;
+        }
+        if self.lmem_resize_to_max {
+            flags |= mcDeviceLmemResizeToMax as c_uint;
+        }
+        flags
+    }
+
+    pub fn from_raw(flags: c_uint) -> Self {
+        use crate::bindings::*;
+        let schedule = match flags & mcDeviceScheduleMask as c_uint {
+            f if f == mcDeviceScheduleSpin as c_uint => Schedule::Spin,
+            f if f == mcDeviceScheduleYield as c_uint => Schedule::Yield,
+            f if f == mcDeviceScheduleBlockingSync as c_uint => Schedule::BlockingSync,
+            _ => Schedule::Auto,
+        };
+        Self {
+            schedule,
+            map_host: flags & mcDeviceMapHost as c_uint != 0,
+            lmem_resize_to_max: flags & mcDeviceLmemResizeToMax as c_uint != 0,
+        }
+    }
+}
+
+pub struct ContextBuilder<'a> {
+    dev: &'a Device,
+    flags: ContextFlags,
+}
+
+impl ContextBuilder<'_> {
     #[inline]
-    pub fn context(&self) -> Context {
+    pub fn flags(mut self, flags: ContextFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    #[inline]
+    pub fn schedule(mut self, schedule: Schedule) -> Self {
+        self.flags.schedule = schedule;
+        self
+    }
+
+    #[inline]
+    pub fn map_host(mut self, map_host: bool) -> Self {
+        self.flags.map_host = map_host;
+        self
+    }
+
+    #[inline]
+    pub fn lmem_resize_to_max(mut self, resize: bool) -> Self {
+        self.flags.lmem_resize_to_max = resize;
+        self
+    }
+
+    pub fn build(self) -> Context {
         const { assert!(size_of::<Context>() == size_of::<[usize; 2]>()) }
         const { assert!(align_of::<Context>() == align_of::<usize>()) }
 
-        let dev = unsafe { self.as_raw() };
+        let dev = unsafe { self.dev.as_raw() };
         let mut ctx = null_mut();
-        mxdrv!(mcCtxCreate(&mut ctx, 0, dev));
+        mxdrv!(mcCtxCreate(&mut ctx, self.flags.to_raw(), dev));
         mxdrv!(mcCtxPopCurrent(null_mut()));
         Context {
             ctx,
@@ -31,26 +123,93 @@ impl Device {
             primary: false,
         }
     }
+}
+
+impl Device {
+    #[inline]
+    pub fn context(&self) -> Context {
+        self.context_builder().build()
+    }
+
+    #[inline]
+    pub fn context_builder(&self) -> ContextBuilder<'_> {
+        ContextBuilder {
+            dev: self,
+            flags: ContextFlags::default(),
+        }
+    }
 
+    /// 设置主上下文的标志，在主上下文激活前调用才能生效于其创建。
     #[inline]
+    pub fn set_primary_flags(&self, flags: ContextFlags) {
+        mxdrv!(mcDevicePrimaryCtxSetFlags(self.as_raw(), flags.to_raw()));
+    }
+
+    /// 持有设备的主上下文。
+    ///
+    /// 进程内对同一设备主上下文的持有是计数的，只有最后一个持有者释放时才重置主上下文。
     pub fn retain_primary(&self) -> Context {
         let dev = unsafe { self.as_raw() };
+        let mut refs = PRIMARY_REFS.lock().unwrap();
         let mut ctx = null_mut();
         mxdrv!(mcDevicePrimaryCtxRetain(&mut ctx, dev));
+        *refs.entry(dev).or_insert(0) += 1;
         Context {
             ctx,
             dev,
             primary: true,
         }
     }
+
+    pub fn primary_state(&self) -> PrimaryState {
+        let mut flags = 0;
+        let mut active = 0;
+        mxdrv!(mcDevicePrimaryCtxGetState(
+            self.as_raw(),
+            &mut flags,
+            &mut active
+        ));
+        PrimaryState {
+            flags: ContextFlags::from_raw(flags),
+            active: active != 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PrimaryState {
+    pub flags: ContextFlags,
+    pub active: bool,
+}
+
+/// 进程内各设备主上下文的持有计数。
+static PRIMARY_REFS: Mutex<BTreeMap<mcDevice_t, usize>> = Mutex::new(BTreeMap::new());
+
+/// 串行化依赖进程内主上下文状态的测试。
+#[cfg(test)]
+pub(crate) static PRIMARY_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// 减少主上下文的持有计数，返回是否是最后一个持有者。
+fn release_primary(dev: mcDevice_t) -> bool {
+    let mut refs = PRIMARY_REFS.lock().unwrap();
+    let count = refs.get_mut(&dev).unwrap();
+    *count -= 1;
+    if *count == 0 {
+        refs.remove(&dev);
+        true
+    } else {
+        false
+    }
 }
 
 impl Drop for Context {
     #[inline]
     fn drop(&mut self) {
         if self.primary {
-            // mcDevicePrimaryCtxRelease 这个函数api中没有，但是有记录
-            mxdrv!(mcDevicePrimaryCtxReset(self.dev));
+            // mcDevicePrimaryCtxRelease 这个函数api中没有，但是有记录，只能在最后一个持有者释放时重置
+            if release_primary(self.dev) {
+                mxdrv!(mcDevicePrimaryCtxReset(self.dev));
+            }
         } else {
             mxdrv!(mcCtxDestroy(self.ctx))
         }
@@ -74,14 +233,91 @@ impl Context {
         Device::new(self.dev)
     }
 
+    #[inline]
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+
+    /// 立即重置主上下文，销毁其上的所有资源。
+    ///
+    /// 进程内还有其他持有者时不重置，原样返回上下文。
+    pub fn reset(self) -> Result<(), Self> {
+        assert!(self.primary, "Only primary context can be reset");
+        let mut refs = PRIMARY_REFS.lock().unwrap();
+        if refs[&self.dev] > 1 {
+            drop(refs);
+            return Err(self);
+        }
+        refs.remove(&self.dev);
+        mxdrv!(mcDevicePrimaryCtxReset(self.dev));
+        drop(refs);
+        forget(self);
+        Ok(())
+    }
+
+    pub fn flags(&self) -> ContextFlags {
+        let mut flags = 0;
+        if self.primary {
+            let mut active = 0;
+            mxdrv!(mcDevicePrimaryCtxGetState(
+                self.dev,
+                &mut flags,
+                &mut active
+            ));
+        } else {
+            self.apply(|_| mxdrv!(mcCtxGetFlags(&mut flags)));
+        }
+        ContextFlags::from_raw(flags)
+    }
+
     #[inline]
     pub fn apply<T>(&self, f: impl FnOnce(&CurrentCtx) -> T) -> T {
+        f(&self.push())
+    }
+
+    /// 将上下文压入当前线程的上下文栈，返回的守卫在析构时（包括栈展开时）将其弹出。
+    ///
+    /// 可以嵌套调用，守卫必须按压入的逆序析构，否则析构时 panic。
+    #[inline]
+    pub fn push(&self) -> CtxGuard<'_> {
         mxdrv!(mcCtxPushCurrent(self.ctx));
-        let ans = f(&CurrentCtx(self.ctx));
+        CURRENT.with_borrow_mut(|stack| stack.push(self.ctx));
+        CtxGuard(CurrentCtx(self.ctx), PhantomData)
+    }
+}
+
+thread_local! {
+    /// 本线程通过 [`Context::push`] 压入的上下文，栈顶即当前上下文，省去查询驱动。
+    ///
+    /// 在 `apply` 内直接调用绑定压入或设置上下文的代码必须在返回前恢复原状。
+    static CURRENT: RefCell<Vec<MCcontext>> = const { RefCell::new(Vec::new()) };
+}
+
+/// 上下文压栈的守卫，只能在压栈的线程上使用。
+pub struct CtxGuard<'a>(CurrentCtx, PhantomData<(&'a Context, *const ())>);
+
+impl Deref for CtxGuard<'_> {
+    type Target = CurrentCtx;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for CtxGuard<'_> {
+    fn drop(&mut self) {
+        CURRENT.with_borrow_mut(|stack| stack.pop());
         let mut top = null_mut();
-        mxdrv!(mcCtxPopCurrent(&mut top));
-        assert_eq!(top, self.ctx);
-        ans
+        if std::thread::panicking() {
+            // 展开时不能再次 panic，尽力恢复上下文栈
+            let _ = unsafe { crate::bindings::mcCtxPopCurrent(&mut top) };
+        } else {
+            mxdrv!(mcCtxPopCurrent(&mut top));
+            assert_eq!(
+                top, self.0 .0,
+                "context stack corrupted: guards dropped out of order or a context left pushed"
+            );
+        }
     }
 }
 
@@ -114,8 +350,14 @@ impl CurrentCtx {
 
     #[inline]
     pub fn apply_current<T>(f: impl FnOnce(&Self) -> T) -> Result<T, NoCtxError> {
-        let mut raw = null_mut();
-        mxdrv!(mcCtxGetCurrent(&mut raw));
+        let raw = match CURRENT.with_borrow(|stack| stack.last().copied()) {
+            Some(raw) => raw,
+            None => {
+                let mut raw = null_mut();
+                mxdrv!(mcCtxGetCurrent(&mut raw));
+                raw
+            }
+        };
         if !raw.is_null() {
             Ok(f(&Self(raw)))
         } else {
@@ -170,11 +412,86 @@ impl CurrentCtx {
     }
 }
 
+#[test]
+fn test_flags() {
+    let flags = ContextFlags {
+        schedule: Schedule::BlockingSync,
+        map_host: true,
+        lmem_resize_to_max: false,
+    };
+    assert_eq!(ContextFlags::from_raw(flags.to_raw()), flags);
+
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    let ctx = Device::new(0)
+        .context_builder()
+        .schedule(Schedule::Spin)
+        .build();
+    assert_eq!(ctx.flags().schedule, Schedule::Spin);
+}
+
+#[test]
+fn test_guard() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    let a = Device::new(0).context();
+    let b = Device::new(0).context();
+    a.apply(|ctx_a| {
+        b.apply(|ctx_b| {
+            let raw = CurrentCtx::apply_current(|ctx| unsafe { ctx.as_raw() }).unwrap();
+            assert_eq!(raw, unsafe { ctx_b.as_raw() });
+        });
+        let raw = CurrentCtx::apply_current(|ctx| unsafe { ctx.as_raw() }).unwrap();
+        assert_eq!(raw, unsafe { ctx_a.as_raw() });
+    });
+
+    {
+        let guard_a = a.push();
+        let guard_b = b.push();
+        let raw = CurrentCtx::apply_current(|ctx| unsafe { ctx.as_raw() }).unwrap();
+        assert_eq!(raw, unsafe { guard_b.as_raw() });
+        drop(guard_b);
+        let raw = CurrentCtx::apply_current(|ctx| unsafe { ctx.as_raw() }).unwrap();
+        assert_eq!(raw, unsafe { guard_a.as_raw() });
+    }
+
+    let result = catch_unwind(AssertUnwindSafe(|| a.apply(|_| panic!("in context"))));
+    assert!(result.is_err());
+    assert_eq!(CurrentCtx::apply_current(|_| ()), Err(NoCtxError));
+}
+
+#[test]
+fn test_primary_refs() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    let _lock = PRIMARY_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dev = Device::new(0);
+    let a = dev.retain_primary();
+    let b = dev.retain_primary();
+    assert!(dev.primary_state().active);
+    drop(a);
+    assert!(dev.primary_state().active);
+    let b = b.reset().unwrap_err();
+    assert!(dev.primary_state().active);
+    drop(b);
+    assert!(!dev.primary_state().active);
+
+    let c = dev.retain_primary();
+    assert!(c.reset().is_ok());
+    assert!(!dev.primary_state().active);
+}
+
 #[test]
 fn test_primary() {
     if let Err(crate::NoDevice) = crate::init() {
         return;
     }
+    let _lock = PRIMARY_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
     let dev = crate::Device::new(0);
     let mut flags = 0;
     let mut active = 0;
@@ -0,0 +1,262 @@
+use crate::{Context, CurrentCtx, Device, Stream, StreamSpore};
+use context_spore::{ContextResource, ContextSpore};
+use std::{
+    any::Any,
+    sync::{Condvar, Mutex},
+    thread,
+};
+
+/// 一组设备上的上下文，用于数据并行或张量并行的多卡任务。
+///
+/// 每个上下文附带一个流，以孢子形式保存在组中。
+pub struct ContextGroup {
+    contexts: Vec<Context>,
+    streams: Vec<Option<StreamSpore>>,
+}
+
+/// [`ContextGroup::run`] 中单个设备的执行环境。
+pub struct GroupMember<'a> {
+    index: usize,
+    ctx: &'a CurrentCtx,
+    stream: &'a Stream<'a>,
+    barrier: &'a GroupBarrier,
+}
+
+/// 组内成员的屏障，任一成员 panic 后，正在等待和之后到达的成员都会 panic，而不是永远等待。
+struct GroupBarrier {
+    n: usize,
+    state: Mutex<BarrierState>,
+    cvar: Condvar,
+}
+
+struct BarrierState {
+    count: usize,
+    generation: usize,
+    poisoned: bool,
+}
+
+const POISONED: &str = "another member of the context group panicked";
+
+impl GroupBarrier {
+    fn new(n: usize) -> Self {
+        Self {
+            n,
+            state: Mutex::new(BarrierState {
+                count: 0,
+                generation: 0,
+                poisoned: false,
+            }),
+            cvar: Condvar::new(),
+        }
+    }
+
+    fn wait(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.poisoned {
+            drop(state);
+            std::panic::panic_any(POISONED)
+        }
+        state.count += 1;
+        if state.count == self.n {
+            state.count = 0;
+            state.generation += 1;
+            self.cvar.notify_all();
+            return;
+        }
+        let generation = state.generation;
+        let state = self
+            .cvar
+            .wait_while(state, |s| s.generation == generation && !s.poisoned)
+            .unwrap();
+        if state.generation == generation {
+            drop(state);
+            std::panic::panic_any(POISONED)
+        }
+    }
+
+    fn poison(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.poisoned = true;
+        self.cvar.notify_all();
+    }
+}
+
+fn is_poisoned(e: &(dyn Any + Send)) -> bool {
+    e.downcast_ref::<&str>() == Some(&POISONED)
+}
+
+/// 成员线程 panic 时毒化屏障。
+struct PoisonOnPanic<'a>(&'a GroupBarrier);
+
+impl Drop for PoisonOnPanic<'_> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            self.0.poison();
+        }
+    }
+}
+
+impl ContextGroup {
+    pub fn new(devices: impl IntoIterator<Item = Device>) -> Self {
+        Self::from_contexts(devices.into_iter().map(|dev| dev.context()).collect())
+    }
+
+    pub fn from_contexts(contexts: Vec<Context>) -> Self {
+        let streams = contexts
+            .iter()
+            .map(|ctx| Some(ctx.apply(|ctx| ctx.stream().sporulate())))
+            .collect();
+        Self { contexts, streams }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.contexts.is_empty()
+    }
+
+    #[inline]
+    pub fn contexts(&self) -> &[Context] {
+        &self.contexts
+    }
+
+    /// 为每个设备启动一个线程，在设备上下文中执行 `f`，按设备顺序返回结果。
+    ///
+    /// 任一成员 panic 时，其他成员在 [`GroupMember::barrier`] 处随之 panic，最终重新抛出最初的 panic。
+    pub fn run<T: Send>(&self, f: impl Fn(&GroupMember) -> T + Sync) -> Vec<T> {
+        let barrier = GroupBarrier::new(self.len());
+        let f = &f;
+        let barrier = &barrier;
+        thread::scope(|s| {
+            let handles = self
+                .contexts
+                .iter()
+                .zip(&self.streams)
+                .enumerate()
+                .map(|(index, (ctx, stream))| {
+                    s.spawn(move || {
+                        let _guard = PoisonOnPanic(barrier);
+                        ctx.apply(|ctx| {
+                            let stream = stream.as_ref().unwrap().sprout_ref(ctx);
+                            f(&GroupMember {
+                                index,
+                                ctx,
+                                stream,
+                                barrier,
+                            })
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+            let mut results = Vec::with_capacity(handles.len());
+            let mut panic = None::<Box<dyn Any + Send>>;
+            for h in handles {
+                match h.join() {
+                    Ok(t) => results.push(t),
+                    // 优先保留最初的 panic，而不是其他成员因屏障毒化产生的 panic
+                    Err(e) => match &panic {
+                        Some(p) if !is_poisoned(&**p) => {}
+                        Some(_) if is_poisoned(&*e) => {}
+                        _ => panic = Some(e),
+                    },
+                }
+            }
+            if let Some(e) = panic {
+                std::panic::resume_unwind(e)
+            }
+            results
+        })
+    }
+
+    /// 同步组中所有上下文。
+    pub fn synchronize(&self) {
+        for ctx in &self.contexts {
+            ctx.apply(|ctx| ctx.synchronize());
+        }
+    }
+}
+
+impl Drop for ContextGroup {
+    fn drop(&mut self) {
+        for (ctx, stream) in self.contexts.iter().zip(&mut self.streams) {
+            if let Some(stream) = stream.take() {
+                ctx.apply(|ctx| drop(stream.sprout(ctx)));
+            }
+        }
+    }
+}
+
+impl<'a> GroupMember<'a> {
+    /// 设备在组中的序号。
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[inline]
+    pub fn ctx(&self) -> &'a CurrentCtx {
+        self.ctx
+    }
+
+    #[inline]
+    pub fn stream(&self) -> &'a Stream<'a> {
+        self.stream
+    }
+
+    /// 同步本设备的流，然后等待组中所有设备到达同一点。
+    pub fn barrier(&self) {
+        self.stream.synchronize();
+        self.barrier.wait();
+    }
+}
+
+#[test]
+fn test_group() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    let group = ContextGroup::new(Device::all());
+    let sums = group.run(|member| {
+        let data = vec![member.index() as u32; 256];
+        let mut dev = member.ctx().malloc::<u32>(data.len());
+        member.stream().memcpy_h2d(&mut dev, &data);
+        member.barrier();
+
+        let mut host = vec![0u32; data.len()];
+        crate::memcpy_d2h(&mut host, &dev);
+        host.iter().sum::<u32>()
+    });
+    assert_eq!(
+        sums,
+        (0..group.len() as u32).map(|i| i * 256).collect::<Vec<_>>()
+    );
+    group.synchronize();
+}
+
+#[test]
+fn test_barrier_poison() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let barrier = GroupBarrier::new(3);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        thread::scope(|s| {
+            for i in 0..3 {
+                let barrier = &barrier;
+                s.spawn(move || {
+                    let _guard = PoisonOnPanic(barrier);
+                    barrier.wait();
+                    if i == 0 {
+                        panic!("member 0 failed");
+                    }
+                    barrier.wait();
+                });
+            }
+        })
+    }));
+    assert!(result.is_err());
+    assert!(barrier.state.lock().unwrap().poisoned);
+}
@@ -1,12 +1,9 @@
 use crate::{
-    bindings::{
-        mcDeviceAttribute_t::{self, *},
-        mcDevice_t,
-    },
+    bindings::{mcDeviceAttribute_t, mcDevice_t},
     Dim3, MemSize, Version,
 };
 use context_spore::AsRaw;
-use std::{ffi::c_int, fmt};
+use std::ffi::c_int;
 
 #[repr(transparent)]
 pub struct Device(mcDevice_t);
@@ -34,6 +31,12 @@ impl Device {
         count as _
     }
 
+    /// 按序号枚举驱动可见的所有设备。
+    #[inline]
+    pub fn all() -> impl ExactSizeIterator<Item = Self> + Clone {
+        (0..Self::count()).map(|i| Self::new(i as _))
+    }
+
     pub fn name(&self) -> String {
         let mut name = [0u8; 256];
         mxdrv!(mcDeviceGetName(
@@ -47,8 +50,8 @@ impl Device {
     #[inline]
     pub fn compute_capability(&self) -> Version {
         Version {
-            major: self.get_attribute(mcDeviceAttributeComputeCapabilityMajor),
-            minor: self.get_attribute(mcDeviceAttributeComputeCapabilityMinor),
+            major: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeComputeCapabilityMajor),
+            minor: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeComputeCapabilityMinor),
         }
     }
 
@@ -59,122 +62,149 @@ impl Device {
         bytes.into()
     }
 
+    #[inline]
+    pub fn l2_cache_size(&self) -> MemSize {
+        self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeL2CacheSize)
+            .into()
+    }
+
     #[inline]
     pub fn alignment(&self) -> usize {
-        self.get_attribute(mcDeviceAttributeTextureAlignment) as _
+        self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeTextureAlignment) as _
     }
 
     #[inline]
     pub fn warp_size(&self) -> usize {
-        self.get_attribute(mcDeviceAttributeWarpSize) as _
+        self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeWarpSize) as _
     }
 
     #[inline]
     pub fn sm_count(&self) -> usize {
-        self.get_attribute(mcDeviceAttributeMultiProcessorCount) as _
+        self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMultiProcessorCount) as _
     }
 
     pub fn max_grid_dims(&self) -> Dim3 {
         Dim3 {
-            x: self.get_attribute(mcDeviceAttributeMaxGridDimX) as _,
-            y: self.get_attribute(mcDeviceAttributeMaxGridDimY) as _,
-            z: self.get_attribute(mcDeviceAttributeMaxGridDimZ) as _,
+            x: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxGridDimX) as _,
+            y: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxGridDimY) as _,
+            z: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxGridDimZ) as _,
         }
     }
 
     pub fn block_limit(&self) -> BlockLimit {
         BlockLimit {
-            max_threads: self.get_attribute(mcDeviceAttributeMaxThreadsPerBlock) as _,
+            max_threads: self
+                .get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxThreadsPerBlock)
+                as _,
             max_dims: Dim3 {
-                x: self.get_attribute(mcDeviceAttributeMaxBlockDimX) as _,
-                y: self.get_attribute(mcDeviceAttributeMaxBlockDimY) as _,
-                z: self.get_attribute(mcDeviceAttributeMaxBlockDimZ) as _,
+                x: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxBlockDimX) as _,
+                y: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxBlockDimY) as _,
+                z: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxBlockDimZ) as _,
             },
             max_smem: self
-                .get_attribute(mcDeviceAttributeMaxSharedMemoryPerBlock)
+                .get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxSharedMemoryPerBlock)
                 .into(),
             max_registers: self
-                .get_attribute(mcDeviceAttributeMaxRegistersPerBlock)
+                .get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxRegistersPerBlock)
                 .into(),
         }
     }
 
     pub fn sm_limit(&self) -> SMLimit {
         SMLimit {
-            max_blocks: self.get_attribute(mcDevAttrMaxBlocksPerMultiprocessor) as _,
-            max_threads: self.get_attribute(mcDeviceAttributeMaxThreadsPerMultiProcessor) as _,
+            max_blocks: self.get_attribute(mcDeviceAttribute_t::mcDevAttrMaxBlocksPerMultiprocessor)
+                as _,
+            max_threads: self
+                .get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxThreadsPerMultiProcessor)
+                as _,
             max_smem: self
-                .get_attribute(mcDeviceAttributeMaxSharedMemoryPerMultiprocessor)
+                .get_attribute(
+                    mcDeviceAttribute_t::mcDeviceAttributeMaxSharedMemoryPerMultiprocessor,
+                )
                 .into(),
             max_registers: self
-                .get_attribute(mcDeviceAttributeMaxRegistersPerMultiprocessor)
+                .get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMaxRegistersPerMultiprocessor)
                 .into(),
         }
     }
 
+    pub fn clock_rate(&self) -> ClockRate {
+        ClockRate {
+            core_khz: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeClockRate) as _,
+            memory_khz: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMemoryClockRate)
+                as _,
+        }
+    }
+
+    pub fn memory_limit(&self) -> MemoryLimit {
+        MemoryLimit {
+            total: self.total_memory(),
+            constant: self
+                .get_attribute(mcDeviceAttribute_t::mcDeviceAttributeTotalConstantMemory)
+                .into(),
+            l2_cache: self.l2_cache_size(),
+            bus_width: self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeMemoryBusWidth)
+                as _,
+        }
+    }
+
+    pub fn features(&self) -> Features {
+        let flag = |attr| self.get_attribute(attr) != 0;
+        Features {
+            concurrent_kernels: flag(mcDeviceAttribute_t::mcDeviceAttributeConcurrentKernels),
+            ecc: flag(mcDeviceAttribute_t::mcDeviceAttributeEccEnabled),
+            unified_addressing: flag(mcDeviceAttribute_t::mcDeviceAttributeUnifiedAddressing),
+            managed_memory: flag(mcDeviceAttribute_t::mcDeviceAttributeManagedMemory),
+            concurrent_managed_access: flag(
+                mcDeviceAttribute_t::mcDeviceAttributeConcurrentManagedAccess,
+            ),
+            can_map_host_memory: flag(mcDeviceAttribute_t::mcDeviceAttributeCanMapHostMemory),
+            integrated: flag(mcDeviceAttribute_t::mcDeviceAttributeIntegrated),
+            cooperative_launch: flag(mcDeviceAttribute_t::mcDeviceAttributeCooperativeLaunch),
+            kernel_exec_timeout: flag(mcDeviceAttribute_t::mcDeviceAttributeKernelExecTimeout),
+            async_engines: self
+                .get_attribute(mcDeviceAttribute_t::mcDeviceAttributeAsyncEngineCount)
+                as _,
+        }
+    }
+
     #[inline]
-    pub fn info(&self) -> InfoFmt {
-        InfoFmt(self)
+    pub fn compute_mode(&self) -> ComputeMode {
+        match self.get_attribute(mcDeviceAttribute_t::mcDeviceAttributeComputeMode) {
+            0 => ComputeMode::Default,
+            1 => ComputeMode::Exclusive,
+            2 => ComputeMode::Prohibited,
+            3 => ComputeMode::ExclusiveProcess,
+            other => ComputeMode::Unknown(other),
+        }
     }
 
+    /// 一次性查询所有分组的属性。
+    pub fn attributes(&self) -> Attributes {
+        Attributes {
+            compute_capability: self.compute_capability(),
+            alignment: self.alignment(),
+            warp_size: self.warp_size(),
+            sm_count: self.sm_count(),
+            clock_rate: self.clock_rate(),
+            memory_limit: self.memory_limit(),
+            block_limit: self.block_limit(),
+            sm_limit: self.sm_limit(),
+            max_grid_dims: self.max_grid_dims(),
+            features: self.features(),
+            compute_mode: self.compute_mode(),
+        }
+    }
+
+    /// 查询任意一项设备属性的原始值。
     #[inline]
-    fn get_attribute(&self, attr: mcDeviceAttribute_t) -> c_int {
+    pub fn get_attribute(&self, attr: mcDeviceAttribute_t) -> c_int {
         let mut value = 0;
         mxdrv!(mcDeviceGetAttribute(&mut value, attr, self.0));
         value
     }
 }
 
-pub struct InfoFmt<'a>(&'a Device);
-
-impl fmt::Display for InfoFmt<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let block_limit = self.0.block_limit();
-        let sm_limit = self.0.sm_limit();
-        let grid = self.0.max_grid_dims();
-        writeln!(
-            f,
-            "\
-GPU{} ({})
-  cc = {}
-  gmem = {}
-  alignment = {}
-  warp size = {}
-  sm count = {}
-  block limit
-    threads = {} (x: {}, y: {}, z: {})
-    smem = {}
-    registers = {}
-  sm limit
-    blocks = {}
-    threads = {}
-    smem = {}
-    registers = {}
-  grid = (x: {}, y: {}, z: {})",
-            self.0 .0,
-            self.0.name(),
-            self.0.compute_capability(),
-            self.0.total_memory(),
-            self.0.alignment(),
-            self.0.warp_size(),
-            self.0.sm_count(),
-            block_limit.max_threads,
-            block_limit.max_dims.x,
-            block_limit.max_dims.y,
-            block_limit.max_dims.z,
-            block_limit.max_smem,
-            block_limit.max_registers,
-            sm_limit.max_blocks,
-            sm_limit.max_threads,
-            sm_limit.max_smem,
-            sm_limit.max_registers,
-            grid.x,
-            grid.y,
-            grid.z,
-        )
-    }
-}
-
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct BlockLimit {
     pub max_threads: usize,
@@ -191,12 +221,66 @@ pub struct SMLimit {
     pub max_registers: MemSize,
 }
 
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ClockRate {
+    pub core_khz: usize,
+    pub memory_khz: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MemoryLimit {
+    pub total: MemSize,
+    pub constant: MemSize,
+    pub l2_cache: MemSize,
+    /// 显存总线位宽，单位为 bit。
+    pub bus_width: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Features {
+    pub concurrent_kernels: bool,
+    pub ecc: bool,
+    pub unified_addressing: bool,
+    pub managed_memory: bool,
+    pub concurrent_managed_access: bool,
+    pub can_map_host_memory: bool,
+    pub integrated: bool,
+    pub cooperative_launch: bool,
+    pub kernel_exec_timeout: bool,
+    pub async_engines: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ComputeMode {
+    Default,
+    Exclusive,
+    Prohibited,
+    ExclusiveProcess,
+    Unknown(c_int),
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Attributes {
+    pub compute_capability: Version,
+    pub alignment: usize,
+    pub warp_size: usize,
+    pub sm_count: usize,
+    pub clock_rate: ClockRate,
+    pub memory_limit: MemoryLimit,
+    pub block_limit: BlockLimit,
+    pub sm_limit: SMLimit,
+    pub max_grid_dims: Dim3,
+    pub features: Features,
+    pub compute_mode: ComputeMode,
+}
+
 #[test]
 fn test() {
     if let Err(crate::NoDevice) = crate::init() {
         return;
     }
-    for i in 0..Device::count() {
-        println!("{}", Device::new(i as _).info());
+    for dev in Device::all() {
+        println!("{}", dev.info());
+        println!("{:#?}", dev.attributes());
     }
 }
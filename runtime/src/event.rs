@@ -8,8 +8,15 @@ impl<'ctx> Stream<'ctx> {
     pub fn record(&self) -> Event<'ctx> {
         let mut event = null_mut();
         mxdrv!(mcEventCreate(&mut event));
+        mxdrv!(mcEventRecord(event, self.as_raw()));
         Event(unsafe { self.ctx().wrap_raw(event) }, PhantomData)
     }
+
+    /// 在流上重新记录一个已有的事件，之前对它的等待不受影响。
+    #[inline]
+    pub fn rerecord(&self, event: &mut Event) {
+        mxdrv!(mcEventRecord(event.0.rss, self.as_raw()));
+    }
 }
 
 impl Drop for Event<'_> {
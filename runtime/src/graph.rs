@@ -0,0 +1,152 @@
+use crate::{
+    bindings::{
+        mcGraphExecUpdateResult, mcGraphExec_t, mcGraph_t, mcStreamCaptureMode,
+        mcStreamCaptureStatus,
+    },
+    CurrentCtx, Stream,
+};
+use context_spore::{impl_spore, AsRaw};
+use std::{marker::PhantomData, ptr::null_mut};
+
+impl_spore!(Graph and GraphSpore by (CurrentCtx, mcGraph_t));
+impl_spore!(GraphExec and GraphExecSpore by (CurrentCtx, mcGraphExec_t));
+
+/// 流捕获模式，决定捕获期间其他线程中不安全的调用是否被禁止。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CaptureMode {
+    Global,
+    ThreadLocal,
+    Relaxed,
+}
+
+impl From<CaptureMode> for mcStreamCaptureMode {
+    #[inline]
+    fn from(mode: CaptureMode) -> Self {
+        match mode {
+            CaptureMode::Global => Self::mcStreamCaptureModeGlobal,
+            CaptureMode::ThreadLocal => Self::mcStreamCaptureModeThreadLocal,
+            CaptureMode::Relaxed => Self::mcStreamCaptureModeRelaxed,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GraphUpdateError(pub mcGraphExecUpdateResult);
+
+impl CurrentCtx {
+    /// 创建一个空图。
+    #[inline]
+    pub fn graph(&self) -> Graph<'_> {
+        let mut graph = null_mut();
+        mxdrv!(mcGraphCreate(&mut graph, 0));
+        Graph(unsafe { self.wrap_raw(graph) }, PhantomData)
+    }
+}
+
+impl<'ctx> Stream<'ctx> {
+    #[inline]
+    pub fn begin_capture(&self, mode: CaptureMode) {
+        mxdrv!(mcStreamBeginCapture(self.as_raw(), mode.into()));
+    }
+
+    #[inline]
+    pub fn end_capture(&self) -> Graph<'ctx> {
+        let mut graph = null_mut();
+        mxdrv!(mcStreamEndCapture(self.as_raw(), &mut graph));
+        Graph(unsafe { self.ctx().wrap_raw(graph) }, PhantomData)
+    }
+
+    /// 捕获 `f` 提交到流上的所有任务。
+    pub fn capture(&self, mode: CaptureMode, f: impl FnOnce(&Self)) -> Graph<'ctx> {
+        self.begin_capture(mode);
+        f(self);
+        self.end_capture()
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        let mut status = mcStreamCaptureStatus::mcStreamCaptureStatusNone;
+        mxdrv!(mcStreamIsCapturing(self.as_raw(), &mut status));
+        !matches!(status, mcStreamCaptureStatus::mcStreamCaptureStatusNone)
+    }
+}
+
+impl Drop for Graph<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        mxdrv!(mcGraphDestroy(self.0.rss));
+    }
+}
+
+impl AsRaw for Graph<'_> {
+    type Raw = mcGraph_t;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0.rss
+    }
+}
+
+impl<'ctx> Graph<'ctx> {
+    pub fn instantiate(&self) -> GraphExec<'ctx> {
+        let mut exec = null_mut();
+        mxdrv!(mcGraphInstantiateWithFlags(&mut exec, self.0.rss, 0));
+        GraphExec(unsafe { self.ctx().wrap_raw(exec) }, PhantomData)
+    }
+}
+
+impl Drop for GraphExec<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        mxdrv!(mcGraphExecDestroy(self.0.rss));
+    }
+}
+
+impl AsRaw for GraphExec<'_> {
+    type Raw = mcGraphExec_t;
+    #[inline]
+    unsafe fn as_raw(&self) -> Self::Raw {
+        self.0.rss
+    }
+}
+
+impl GraphExec<'_> {
+    #[inline]
+    pub fn launch(&self, stream: &Stream) {
+        mxdrv!(mcGraphLaunch(self.0.rss, stream.as_raw()));
+    }
+
+    /// 以拓扑相同的 `graph` 原地更新可执行图中的节点参数。
+    ///
+    /// 拓扑改变时更新失败，可执行图保持原状，需要重新实例化。
+    pub fn update(&mut self, graph: &Graph) -> Result<(), GraphUpdateError> {
+        use crate::bindings::{mcError_t, mcGraphExecUpdate};
+        let mut node = null_mut();
+        let mut result = mcGraphExecUpdateResult::mcGraphExecUpdateSuccess;
+        match unsafe { mcGraphExecUpdate(self.0.rss, graph.0.rss, &mut node, &mut result) } {
+            mcError_t::mcSuccess => Ok(()),
+            mcError_t::mcErrorGraphExecUpdateFailure => Err(GraphUpdateError(result)),
+            e => panic!("Failed to update graph: {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_capture() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    crate::Device::new(0).context().apply(|ctx| {
+        let stream = ctx.stream();
+        let data = [1u32, 2, 3, 4];
+        let src = ctx.from_host(&data);
+        let mut dst = ctx.malloc::<u32>(data.len());
+        let graph = stream.capture(CaptureMode::ThreadLocal, |s| s.memcpy_d2d(&mut dst, &src));
+        assert!(!stream.is_capturing());
+        let exec = graph.instantiate();
+        exec.launch(&stream);
+        stream.synchronize();
+
+        let mut host = [0u32; 4];
+        crate::memcpy_d2h(&mut host, &dst);
+        assert_eq!(host, data);
+    });
+}
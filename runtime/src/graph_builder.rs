@@ -0,0 +1,427 @@
+use crate::{
+    bindings::{
+        dim3, mcGraphNode_t, mcGraph_t, mcHostNodeParams, mcKernelNodeParams, mcMemcpyKind,
+        mcMemsetParams,
+    },
+    CurrentCtx, DevByte, Dim3, Event, Graph, GraphExec,
+};
+use context_spore::AsRaw;
+use std::{
+    ffi::c_void,
+    fmt::Write,
+    mem::size_of_val,
+    ptr::null_mut,
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+/// 逐节点构造计算图。
+///
+/// 构造器在原始图之外记录节点类型和依赖关系，以便导出 DOT 格式用于调试。
+pub struct GraphBuilder<'ctx> {
+    graph: Graph<'ctx>,
+    id: usize,
+    nodes: Vec<Node>,
+}
+
+/// 图中节点的句柄，只在创建它的 [`GraphBuilder`] 中有效，传给其他构造器时 panic。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GraphNode {
+    builder: usize,
+    index: usize,
+}
+
+/// 为每个构造器分配不同的编号，用于识别节点的来源。
+static NEXT_BUILDER_ID: AtomicUsize = AtomicUsize::new(0);
+
+struct Node {
+    raw: mcGraphNode_t,
+    kind: &'static str,
+    deps: Vec<usize>,
+}
+
+impl CurrentCtx {
+    pub fn graph_builder(&self) -> GraphBuilder<'_> {
+        GraphBuilder {
+            graph: self.graph(),
+            id: NEXT_BUILDER_ID.fetch_add(1, Relaxed),
+            nodes: Vec::new(),
+        }
+    }
+}
+
+impl<'ctx> GraphBuilder<'ctx> {
+    #[inline]
+    pub fn graph(&self) -> &Graph<'ctx> {
+        &self.graph
+    }
+
+    #[inline]
+    pub fn build(self) -> Graph<'ctx> {
+        self.graph
+    }
+
+    #[inline]
+    pub fn instantiate(&self) -> GraphExec<'ctx> {
+        self.graph.instantiate()
+    }
+
+    pub fn add_empty(&mut self, deps: &[GraphNode]) -> GraphNode {
+        let (raw_deps, graph) = self.prepare(deps);
+        let mut node = null_mut();
+        mxdrv!(mcGraphAddEmptyNode(
+            &mut node,
+            graph,
+            raw_deps.as_ptr(),
+            raw_deps.len()
+        ));
+        self.push(node, "empty", deps)
+    }
+
+    /// 添加一个核函数节点。
+    ///
+    /// # Safety
+    ///
+    /// `func` must be a valid kernel function handle, and `params` must match its signature
+    /// and stay valid until the graph is instantiated.
+    pub unsafe fn add_kernel(
+        &mut self,
+        deps: &[GraphNode],
+        func: *const c_void,
+        grid_dims: impl Into<Dim3>,
+        block_dims: impl Into<Dim3>,
+        shared_mem: usize,
+        params: &[*const c_void],
+    ) -> GraphNode {
+        let params = mcKernelNodeParams {
+            func: func as _,
+            gridDim: to_dim3(grid_dims.into()),
+            blockDim: to_dim3(block_dims.into()),
+            sharedMemBytes: shared_mem as _,
+            kernelParams: params.as_ptr() as _,
+            extra: null_mut(),
+        };
+        let (raw_deps, graph) = self.prepare(deps);
+        let mut node = null_mut();
+        mxdrv!(mcGraphAddKernelNode(
+            &mut node,
+            graph,
+            raw_deps.as_ptr(),
+            raw_deps.len(),
+            &params
+        ));
+        self.push(node, "kernel", deps)
+    }
+
+    /// 添加一个主机到设备的拷贝节点。
+    ///
+    /// # Safety
+    ///
+    /// `dst` and `src` must stay valid and must not be reallocated until every graph
+    /// instantiated from this builder is no longer launched.
+    pub unsafe fn add_memcpy_h2d<T: Copy>(
+        &mut self,
+        deps: &[GraphNode],
+        dst: &mut [DevByte],
+        src: &[T],
+    ) -> GraphNode {
+        let len = size_of_val(src);
+        assert_eq!(len, size_of_val(dst));
+        self.add_memcpy(
+            deps,
+            dst.as_mut_ptr().cast(),
+            src.as_ptr().cast(),
+            len,
+            mcMemcpyKind::mcMemcpyHostToDevice,
+            "memcpy h2d",
+        )
+    }
+
+    /// 添加一个设备到主机的拷贝节点。
+    ///
+    /// # Safety
+    ///
+    /// `dst` and `src` must stay valid and must not be reallocated until every graph
+    /// instantiated from this builder is no longer launched.
+    pub unsafe fn add_memcpy_d2h<T: Copy>(
+        &mut self,
+        deps: &[GraphNode],
+        dst: &mut [T],
+        src: &[DevByte],
+    ) -> GraphNode {
+        let len = size_of_val(dst);
+        assert_eq!(len, size_of_val(src));
+        self.add_memcpy(
+            deps,
+            dst.as_mut_ptr().cast(),
+            src.as_ptr().cast(),
+            len,
+            mcMemcpyKind::mcMemcpyDeviceToHost,
+            "memcpy d2h",
+        )
+    }
+
+    /// 添加一个设备到设备的拷贝节点。
+    ///
+    /// # Safety
+    ///
+    /// `dst` and `src` must stay valid and must not be reallocated until every graph
+    /// instantiated from this builder is no longer launched.
+    pub unsafe fn add_memcpy_d2d(
+        &mut self,
+        deps: &[GraphNode],
+        dst: &mut [DevByte],
+        src: &[DevByte],
+    ) -> GraphNode {
+        let len = size_of_val(src);
+        assert_eq!(len, size_of_val(dst));
+        self.add_memcpy(
+            deps,
+            dst.as_mut_ptr().cast(),
+            src.as_ptr().cast(),
+            len,
+            mcMemcpyKind::mcMemcpyDeviceToDevice,
+            "memcpy d2d",
+        )
+    }
+
+    /// 添加一个设备存储置值节点。
+    ///
+    /// # Safety
+    ///
+    /// `dst` must stay valid and must not be reallocated until every graph
+    /// instantiated from this builder is no longer launched.
+    pub unsafe fn add_memset(
+        &mut self,
+        deps: &[GraphNode],
+        dst: &mut [DevByte],
+        value: u8,
+    ) -> GraphNode {
+        let params = mcMemsetParams {
+            dst: dst.as_mut_ptr().cast(),
+            pitch: 0,
+            value: value as _,
+            elementSize: 1,
+            width: dst.len(),
+            height: 1,
+        };
+        let (raw_deps, graph) = self.prepare(deps);
+        let mut node = null_mut();
+        mxdrv!(mcGraphAddMemsetNode(
+            &mut node,
+            graph,
+            raw_deps.as_ptr(),
+            raw_deps.len(),
+            &params
+        ));
+        self.push(node, "memset", deps)
+    }
+
+    /// 添加一个主机函数节点。
+    ///
+    /// # Safety
+    ///
+    /// `data` must stay valid for every launch of any graph instantiated from this one,
+    /// and `f` must not call any driver API.
+    pub unsafe fn add_host(
+        &mut self,
+        deps: &[GraphNode],
+        f: unsafe extern "C" fn(*mut c_void),
+        data: *mut c_void,
+    ) -> GraphNode {
+        let params = mcHostNodeParams {
+            fn_: Some(f),
+            userData: data,
+        };
+        let (raw_deps, graph) = self.prepare(deps);
+        let mut node = null_mut();
+        mxdrv!(mcGraphAddHostNode(
+            &mut node,
+            graph,
+            raw_deps.as_ptr(),
+            raw_deps.len(),
+            &params
+        ));
+        self.push(node, "host", deps)
+    }
+
+    /// 添加一个记录事件的节点。
+    ///
+    /// # Safety
+    ///
+    /// `event` must not be destroyed until every graph instantiated from this builder
+    /// is no longer launched.
+    pub unsafe fn add_event_record(&mut self, deps: &[GraphNode], event: &Event) -> GraphNode {
+        let (raw_deps, graph) = self.prepare(deps);
+        let mut node = null_mut();
+        mxdrv!(mcGraphAddEventRecordNode(
+            &mut node,
+            graph,
+            raw_deps.as_ptr(),
+            raw_deps.len(),
+            event.as_raw()
+        ));
+        self.push(node, "event record", deps)
+    }
+
+    /// 添加一个等待事件的节点。
+    ///
+    /// # Safety
+    ///
+    /// `event` must not be destroyed until every graph instantiated from this builder
+    /// is no longer launched.
+    pub unsafe fn add_event_wait(&mut self, deps: &[GraphNode], event: &Event) -> GraphNode {
+        let (raw_deps, graph) = self.prepare(deps);
+        let mut node = null_mut();
+        mxdrv!(mcGraphAddEventWaitNode(
+            &mut node,
+            graph,
+            raw_deps.as_ptr(),
+            raw_deps.len(),
+            event.as_raw()
+        ));
+        self.push(node, "event wait", deps)
+    }
+
+    /// 添加一个子图节点，`child` 在添加时被复制，之后对它的修改不影响本图。
+    pub fn add_child(&mut self, deps: &[GraphNode], child: &Graph) -> GraphNode {
+        let (raw_deps, graph) = self.prepare(deps);
+        let mut node = null_mut();
+        mxdrv!(mcGraphAddChildGraphNode(
+            &mut node,
+            graph,
+            raw_deps.as_ptr(),
+            raw_deps.len(),
+            child.as_raw()
+        ));
+        self.push(node, "child graph", deps)
+    }
+
+    /// 添加一条从 `from` 到 `to` 的依赖边。
+    pub fn add_dependency(&mut self, from: GraphNode, to: GraphNode) {
+        let from = self.index(from);
+        let to = self.index(to);
+        let from_ = self.nodes[from].raw;
+        let to_ = self.nodes[to].raw;
+        mxdrv!(mcGraphAddDependencies(self.graph.as_raw(), &from_, &to_, 1));
+        self.nodes[to].deps.push(from);
+    }
+
+    /// 以 DOT 格式导出图结构。
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            writeln!(dot, "  n{i} [label=\"{i}: {}\"];", node.kind).unwrap();
+        }
+        for (i, node) in self.nodes.iter().enumerate() {
+            for dep in &node.deps {
+                writeln!(dot, "  n{dep} -> n{i};").unwrap();
+            }
+        }
+        dot.push('}');
+        dot
+    }
+
+    fn add_memcpy(
+        &mut self,
+        deps: &[GraphNode],
+        dst: *mut c_void,
+        src: *const c_void,
+        len: usize,
+        kind: mcMemcpyKind,
+        label: &'static str,
+    ) -> GraphNode {
+        let (raw_deps, graph) = self.prepare(deps);
+        let mut node = null_mut();
+        mxdrv!(mcGraphAddMemcpyNode1D(
+            &mut node,
+            graph,
+            raw_deps.as_ptr(),
+            raw_deps.len(),
+            dst,
+            src,
+            len,
+            kind
+        ));
+        self.push(node, label, deps)
+    }
+
+    fn prepare(&self, deps: &[GraphNode]) -> (Vec<mcGraphNode_t>, mcGraph_t) {
+        let raw = deps
+            .iter()
+            .map(|&n| self.nodes[self.index(n)].raw)
+            .collect::<Vec<_>>();
+        (raw, unsafe { self.graph.as_raw() })
+    }
+
+    fn push(&mut self, raw: mcGraphNode_t, kind: &'static str, deps: &[GraphNode]) -> GraphNode {
+        let deps = deps.iter().map(|&n| self.index(n)).collect();
+        self.nodes.push(Node { raw, kind, deps });
+        GraphNode {
+            builder: self.id,
+            index: self.nodes.len() - 1,
+        }
+    }
+
+    fn index(&self, node: GraphNode) -> usize {
+        assert_eq!(
+            node.builder, self.id,
+            "GraphNode belongs to another GraphBuilder"
+        );
+        node.index
+    }
+}
+
+#[inline]
+fn to_dim3(Dim3 { x, y, z }: Dim3) -> dim3 {
+    dim3 { x, y, z }
+}
+
+#[test]
+fn test_build() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    crate::Device::new(0).context().apply(|ctx| {
+        let stream = ctx.stream();
+        let mut dev = ctx.malloc::<u8>(64);
+        let mut host = [0u8; 64];
+
+        let mut builder = ctx.graph_builder();
+        // `dev` 和 `host` 存活到图执行完毕之后
+        let memset = unsafe { builder.add_memset(&[], &mut dev, 7) };
+        let empty = builder.add_empty(&[memset]);
+        let copy = unsafe { builder.add_memcpy_d2h(&[], &mut host, &dev) };
+        builder.add_dependency(empty, copy);
+        assert_eq!(
+            builder.to_dot(),
+            "\
+digraph {
+  n0 [label=\"0: memset\"];
+  n1 [label=\"1: empty\"];
+  n2 [label=\"2: memcpy d2h\"];
+  n0 -> n1;
+  n1 -> n2;
+}"
+        );
+
+        builder.instantiate().launch(&stream);
+        stream.synchronize();
+        assert_eq!(host, [7; 64]);
+    });
+}
+
+#[test]
+fn test_foreign_node() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    crate::Device::new(0).context().apply(|ctx| {
+        let mut a = ctx.graph_builder();
+        let mut b = ctx.graph_builder();
+        let node = a.add_empty(&[]);
+        let result = catch_unwind(AssertUnwindSafe(|| b.add_empty(&[node])));
+        assert!(result.is_err());
+    });
+}
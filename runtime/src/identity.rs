@@ -0,0 +1,272 @@
+use crate::{bindings::mcUUID, Device};
+use context_spore::AsRaw;
+use std::{env::var, ffi::CString, fmt, str::FromStr};
+
+/// 控制可见设备的环境变量。
+///
+/// 驱动自身会按序号过滤设备并重新编号，[`Device::new`]、[`Device::count`] 和 [`Device::all`]
+/// 看到的是过滤后的结果。除序号外，[`Device::visible`] 还支持以 UUID 或 PCI 总线号指定设备，
+/// 这两种标识不随重启或插槽重排而改变。
+pub const VISIBLE_DEVICES_ENV: &str = "MACA_VISIBLE_DEVICES";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Uuid(pub [u8; 16]);
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, b) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                write!(f, "-")?;
+            }
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = InvalidDeviceId;
+
+    /// 解析 `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` 形式的 UUID，允许 `GPU-` 前缀。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("GPU-").unwrap_or(s).as_bytes();
+        if s.len() != 36 {
+            return Err(InvalidDeviceId);
+        }
+        let mut digits = Vec::with_capacity(32);
+        for (i, &c) in s.iter().enumerate() {
+            match (i, c) {
+                (8 | 13 | 18 | 23, b'-') => {}
+                (8 | 13 | 18 | 23, _) => return Err(InvalidDeviceId),
+                _ => digits.push((c as char).to_digit(16).ok_or(InvalidDeviceId)? as u8),
+            }
+        }
+        let mut ans = [0u8; 16];
+        for (b, pair) in ans.iter_mut().zip(digits.chunks(2)) {
+            *b = pair[0] << 4 | pair[1];
+        }
+        Ok(Self(ans))
+    }
+}
+
+/// PCI 总线地址，形如 `domain:bus:device.function`。
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct PciBusId {
+    pub domain: u32,
+    pub bus: u8,
+    pub device: u8,
+    pub function: u8,
+}
+
+impl fmt::Display for PciBusId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04x}:{:02x}:{:02x}.{:x}",
+            self.domain, self.bus, self.device, self.function,
+        )
+    }
+}
+
+impl FromStr for PciBusId {
+    type Err = InvalidDeviceId;
+
+    /// 解析 `dddd:bb:dd.f` 形式的地址，域和功能号可以省略。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = |s: &str| u32::from_str_radix(s, 16).map_err(|_| InvalidDeviceId);
+        let byte = |s: &str| u8::from_str_radix(s, 16).map_err(|_| InvalidDeviceId);
+
+        let (s, function) = match s.split_once('.') {
+            Some((s, f)) => (s, byte(f)?),
+            None => (s, 0),
+        };
+        let parts = s.split(':').collect::<Vec<_>>();
+        let (domain, bus, device) = match parts[..] {
+            [domain, bus, device] => (hex(domain)?, byte(bus)?, byte(device)?),
+            [bus, device] => (0, byte(bus)?, byte(device)?),
+            _ => return Err(InvalidDeviceId),
+        };
+        Ok(Self {
+            domain,
+            bus,
+            device,
+            function,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidDeviceId;
+
+/// 可见设备列表中不随重启改变的设备标识。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum StableId {
+    Uuid(Uuid),
+    PciBusId(PciBusId),
+}
+
+/// 解析可见设备列表。
+///
+/// 只包含序号时返回 `Ok(None)`，表示已由驱动过滤；只包含 UUID 和总线地址时返回这些标识；
+/// 序号与其他标识混用或有无法识别的项时返回错误。
+fn parse_visible(list: &str) -> Result<Option<Vec<StableId>>, InvalidDeviceId> {
+    let items = list
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    if items.iter().all(|s| s.parse::<usize>().is_ok()) {
+        return Ok(None);
+    }
+    items
+        .into_iter()
+        .map(|item| {
+            if let Ok(uuid) = item.parse() {
+                Ok(StableId::Uuid(uuid))
+            } else if item.parse::<usize>().is_err() {
+                item.parse().map(StableId::PciBusId)
+            } else {
+                Err(InvalidDeviceId)
+            }
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+impl Device {
+    pub fn uuid(&self) -> Uuid {
+        let mut uuid = mcUUID { bytes: [0; 16] };
+        mxdrv!(mcDeviceGetUuid(&mut uuid, self.as_raw()));
+        Uuid(uuid.bytes.map(|b| b as _))
+    }
+
+    pub fn pci_bus_id(&self) -> PciBusId {
+        let mut id = [0u8; 64];
+        mxdrv!(mcDeviceGetPCIBusId(
+            id.as_mut_ptr().cast(),
+            id.len() as _,
+            self.as_raw()
+        ));
+        let len = id.iter().position(|&c| c == 0).unwrap_or(id.len());
+        std::str::from_utf8(&id[..len]).unwrap().parse().unwrap()
+    }
+
+    /// 按 PCI 总线地址查找设备，地址格式错误或设备不存在时返回 `None`。
+    pub fn from_pci_bus_id(id: &str) -> Option<Self> {
+        use crate::bindings::{mcDeviceGetByPCIBusId, mcError_t};
+        let id = CString::new(id.parse::<PciBusId>().ok()?.to_string()).unwrap();
+        let mut index = 0;
+        match unsafe { mcDeviceGetByPCIBusId(&mut index, id.as_ptr()) } {
+            mcError_t::mcSuccess => Some(Self::new(index)),
+            mcError_t::mcErrorInvalidDevice | mcError_t::mcErrorInvalidValue => None,
+            e => panic!("Failed to get device by PCI bus id: {e:?}"),
+        }
+    }
+
+    pub fn from_uuid(uuid: &Uuid) -> Option<Self> {
+        Self::all().find(|dev| dev.uuid() == *uuid)
+    }
+
+    /// 按 [`VISIBLE_DEVICES_ENV`] 的设置列出可见设备。
+    ///
+    /// 变量未设置或只包含序号时，驱动已完成过滤，返回驱动枚举的所有设备。
+    /// 只包含 UUID 和 PCI 总线地址时，按顺序在驱动枚举的设备中查找，不存在或重复的项被忽略。
+    /// 序号不能与其他标识混用，混用或有无法识别的项时没有可见设备。
+    pub fn visible() -> Vec<Self> {
+        let Ok(list) = var(VISIBLE_DEVICES_ENV) else {
+            return Self::all().collect();
+        };
+        match parse_visible(&list) {
+            Ok(None) => Self::all().collect(),
+            Ok(Some(ids)) => {
+                let mut ans = Vec::<Self>::new();
+                for id in ids {
+                    let dev = match id {
+                        StableId::Uuid(uuid) => Self::from_uuid(&uuid),
+                        StableId::PciBusId(id) => Self::from_pci_bus_id(&id.to_string()),
+                    };
+                    if let Some(dev) =
+                        dev.filter(|dev| !ans.iter().any(|d| unsafe { d.as_raw() == dev.as_raw() }))
+                    {
+                        ans.push(dev)
+                    }
+                }
+                ans
+            }
+            Err(InvalidDeviceId) => {
+                log::warn!("invalid {VISIBLE_DEVICES_ENV}={list:?}, no device is visible");
+                Vec::new()
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse() {
+    let id = "0000:3b:00.0".parse::<PciBusId>().unwrap();
+    assert_eq!(
+        id,
+        PciBusId {
+            domain: 0,
+            bus: 0x3b,
+            device: 0,
+            function: 0,
+        }
+    );
+    assert_eq!(id.to_string(), "0000:3b:00.0");
+    assert_eq!("3b:00".parse::<PciBusId>(), Ok(id));
+    assert_eq!("3b".parse::<PciBusId>(), Err(InvalidDeviceId));
+
+    let uuid = "GPU-01234567-89ab-cdef-0123-456789abcdef"
+        .parse::<Uuid>()
+        .unwrap();
+    assert_eq!(uuid.to_string(), "01234567-89ab-cdef-0123-456789abcdef");
+    assert_eq!("0123".parse::<Uuid>(), Err(InvalidDeviceId));
+    assert_eq!(
+        "0123456789-ab-cdef-0123-456789abcdef".parse::<Uuid>(),
+        Err(InvalidDeviceId)
+    );
+    assert_eq!(
+        "01234567-89ab-cdef-0123-456789abcdeg".parse::<Uuid>(),
+        Err(InvalidDeviceId)
+    );
+    assert_eq!(
+        "+1234567-89ab-cdef-0123-456789abcdef".parse::<Uuid>(),
+        Err(InvalidDeviceId)
+    );
+}
+
+#[test]
+fn test_parse_visible() {
+    let uuid = "01234567-89ab-cdef-0123-456789abcdef".parse().unwrap();
+    let bus = "3b:00".parse().unwrap();
+    assert_eq!(parse_visible(""), Ok(None));
+    assert_eq!(parse_visible("1, 0"), Ok(None));
+    assert_eq!(
+        parse_visible("GPU-01234567-89ab-cdef-0123-456789abcdef,0000:3b:00.0"),
+        Ok(Some(vec![StableId::Uuid(uuid), StableId::PciBusId(bus)]))
+    );
+    // 序号与其他标识混用
+    assert_eq!(parse_visible("0,3b:00"), Err(InvalidDeviceId));
+    assert_eq!(
+        parse_visible("01234567-89ab-cdef-0123-456789abcdef,1"),
+        Err(InvalidDeviceId)
+    );
+    assert_eq!(parse_visible("3b:00,gpu0"), Err(InvalidDeviceId));
+}
+
+#[test]
+fn test_identity() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    for dev in Device::visible() {
+        let id = dev.pci_bus_id();
+        let uuid = dev.uuid();
+        println!("{} {id} {uuid}", dev.name());
+        assert_eq!(
+            Device::from_pci_bus_id(&id.to_string()).map(|d| d.uuid()),
+            Some(uuid)
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{Attributes, Device, PciBusId, Uuid};
+use context_spore::AsRaw;
+use std::{
+    ffi::c_int,
+    fmt::{self, Write},
+};
+
+/// 设备信息的快照，可以输出为可读文本、JSON 或 `key=value` 行。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DeviceInfo {
+    pub index: c_int,
+    pub name: String,
+    pub uuid: Uuid,
+    pub pci_bus_id: PciBusId,
+    pub attributes: Attributes,
+}
+
+impl Device {
+    pub fn info(&self) -> DeviceInfo {
+        DeviceInfo {
+            index: unsafe { self.as_raw() },
+            name: self.name(),
+            uuid: self.uuid(),
+            pci_bus_id: self.pci_bus_id(),
+            attributes: self.attributes(),
+        }
+    }
+}
+
+impl DeviceInfo {
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.to_value().write_json(&mut json);
+        json
+    }
+
+    /// 每行一项，嵌套的键以 `.` 连接。
+    pub fn to_key_value(&self) -> String {
+        let mut kv = String::new();
+        self.to_value().write_key_value("", &mut kv);
+        kv
+    }
+
+    fn to_value(&self) -> Value {
+        use Value::*;
+        let Attributes {
+            compute_capability,
+            alignment,
+            warp_size,
+            sm_count,
+            clock_rate,
+            memory_limit,
+            block_limit,
+            sm_limit,
+            max_grid_dims,
+            features,
+            compute_mode,
+        } = &self.attributes;
+        let dim3 = |d: &crate::Dim3| {
+            Obj(vec![
+                ("x", Int(d.x as _)),
+                ("y", Int(d.y as _)),
+                ("z", Int(d.z as _)),
+            ])
+        };
+        Obj(vec![
+            ("index", Int(self.index as _)),
+            ("name", Str(self.name.clone())),
+            ("uuid", Str(self.uuid.to_string())),
+            ("pci_bus_id", Str(self.pci_bus_id.to_string())),
+            ("compute_capability", Str(compute_capability.to_string())),
+            ("compute_mode", Str(format!("{compute_mode:?}"))),
+            ("alignment", Int(*alignment as _)),
+            ("warp_size", Int(*warp_size as _)),
+            ("sm_count", Int(*sm_count as _)),
+            (
+                "clock_rate",
+                Obj(vec![
+                    ("core_khz", Int(clock_rate.core_khz as _)),
+                    ("memory_khz", Int(clock_rate.memory_khz as _)),
+                ]),
+            ),
+            (
+                "memory",
+                Obj(vec![
+                    ("total", Int(memory_limit.total.0 as _)),
+                    ("constant", Int(memory_limit.constant.0 as _)),
+                    ("l2_cache", Int(memory_limit.l2_cache.0 as _)),
+                    ("bus_width", Int(memory_limit.bus_width as _)),
+                ]),
+            ),
+            (
+                "block_limit",
+                Obj(vec![
+                    ("max_threads", Int(block_limit.max_threads as _)),
+                    ("max_dims", dim3(&block_limit.max_dims)),
+                    ("max_smem", Int(block_limit.max_smem.0 as _)),
+                    ("max_registers", Int(block_limit.max_registers.0 as _)),
+                ]),
+            ),
+            (
+                "sm_limit",
+                Obj(vec![
+                    ("max_blocks", Int(sm_limit.max_blocks as _)),
+                    ("max_threads", Int(sm_limit.max_threads as _)),
+                    ("max_smem", Int(sm_limit.max_smem.0 as _)),
+                    ("max_registers", Int(sm_limit.max_registers.0 as _)),
+                ]),
+            ),
+            ("max_grid_dims", dim3(max_grid_dims)),
+            (
+                "features",
+                Obj(vec![
+                    ("concurrent_kernels", Bool(features.concurrent_kernels)),
+                    ("ecc", Bool(features.ecc)),
+                    ("unified_addressing", Bool(features.unified_addressing)),
+                    ("managed_memory", Bool(features.managed_memory)),
+                    (
+                        "concurrent_managed_access",
+                        Bool(features.concurrent_managed_access),
+                    ),
+                    ("can_map_host_memory", Bool(features.can_map_host_memory)),
+                    ("integrated", Bool(features.integrated)),
+                    ("cooperative_launch", Bool(features.cooperative_launch)),
+                    ("kernel_exec_timeout", Bool(features.kernel_exec_timeout)),
+                    ("async_engines", Int(features.async_engines as _)),
+                ]),
+            ),
+        ])
+    }
+}
+
+impl fmt::Display for DeviceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Attributes {
+            compute_capability,
+            alignment,
+            warp_size,
+            sm_count,
+            block_limit,
+            sm_limit,
+            max_grid_dims: grid,
+            ..
+        } = &self.attributes;
+        writeln!(
+            f,
+            "\
+GPU{} ({})
+  cc = {}
+  gmem = {}
+  alignment = {}
+  warp size = {}
+  sm count = {}
+  block limit
+    threads = {} (x: {}, y: {}, z: {})
+    smem = {}
+    registers = {}
+  sm limit
+    blocks = {}
+    threads = {}
+    smem = {}
+    registers = {}
+  grid = (x: {}, y: {}, z: {})",
+            self.index,
+            self.name,
+            compute_capability,
+            self.attributes.memory_limit.total,
+            alignment,
+            warp_size,
+            sm_count,
+            block_limit.max_threads,
+            block_limit.max_dims.x,
+            block_limit.max_dims.y,
+            block_limit.max_dims.z,
+            block_limit.max_smem,
+            block_limit.max_registers,
+            sm_limit.max_blocks,
+            sm_limit.max_threads,
+            sm_limit.max_smem,
+            sm_limit.max_registers,
+            grid.x,
+            grid.y,
+            grid.z,
+        )
+    }
+}
+
+enum Value {
+    Str(String),
+    Int(u64),
+    Bool(bool),
+    Obj(Vec<(&'static str, Value)>),
+}
+
+impl Value {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Self::Str(s) => {
+                out.push('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+            Self::Int(n) => write!(out, "{n}").unwrap(),
+            Self::Bool(b) => write!(out, "{b}").unwrap(),
+            Self::Obj(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write!(out, "\"{key}\":").unwrap();
+                    value.write_json(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    fn write_key_value(&self, prefix: &str, out: &mut String) {
+        match self {
+            Self::Str(s) => writeln!(out, "{prefix}={}", s.escape_debug()).unwrap(),
+            Self::Int(n) => writeln!(out, "{prefix}={n}").unwrap(),
+            Self::Bool(b) => writeln!(out, "{prefix}={b}").unwrap(),
+            Self::Obj(fields) => {
+                for (key, value) in fields {
+                    if prefix.is_empty() {
+                        value.write_key_value(key, out)
+                    } else {
+                        value.write_key_value(&format!("{prefix}.{key}"), out)
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_value() {
+    use Value::*;
+    let value = Obj(vec![
+        ("name", Str("a \"b\"\n".into())),
+        ("n", Int(3)),
+        ("limit", Obj(vec![("ok", Bool(true))])),
+    ]);
+
+    let mut json = String::new();
+    value.write_json(&mut json);
+    assert_eq!(
+        json,
+        r#"{"name":"a \"b\"\u000a","n":3,"limit":{"ok":true}}"#
+    );
+
+    let mut kv = String::new();
+    value.write_key_value("", &mut kv);
+    assert_eq!(kv, "name=a \\\"b\\\"\\n\nn=3\nlimit.ok=true\n");
+}
+
+#[test]
+fn test_info() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    for dev in Device::visible() {
+        let info = dev.info();
+        println!("{info}");
+        println!("{}", info.to_json());
+        print!("{}", info.to_key_value());
+    }
+}
@@ -24,23 +24,60 @@ pub mod bindings {
     }
 }
 
+mod bench;
 mod context;
+mod context_group;
 mod device;
 mod event;
+mod graph;
+mod graph_builder;
+mod identity;
+mod info;
+mod limit;
 mod memory;
+mod scheduler;
+mod select;
 mod stream;
+mod stream_pool;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct NoDevice;
+pub enum InitError {
+    /// 没有可用的设备。
+    NoDevice,
+    /// 驱动版本低于构建时使用的工具链版本。
+    DriverTooOld(DriverTooOld),
+}
+
+pub use InitError::NoDevice;
+
+/// 驱动版本低于构建时使用的工具链版本。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DriverTooOld {
+    pub driver: Version,
+    pub toolkit: Version,
+}
 
-pub use context::{Context, CurrentCtx};
+pub use bench::{BenchReport, Benchmark, Budget, Work};
+pub use context::{
+    Context, ContextBuilder, ContextFlags, CtxGuard, CurrentCtx, NoCtxError, PrimaryState, Schedule,
+};
+pub use context_group::{ContextGroup, GroupMember};
 pub use context_spore::{impl_spore, AsRaw, ContextResource, ContextSpore, RawContainer};
-pub use device::{BlockLimit, Device, SMLimit};
+pub use device::{
+    Attributes, BlockLimit, ClockRate, ComputeMode, Device, Features, MemoryLimit, SMLimit,
+};
 pub use event::{Event, EventSpore};
+pub use graph::{CaptureMode, Graph, GraphExec, GraphExecSpore, GraphSpore, GraphUpdateError};
+pub use graph_builder::{GraphBuilder, GraphNode};
+pub use identity::{InvalidDeviceId, PciBusId, Uuid, VISIBLE_DEVICES_ENV};
+pub use info::DeviceInfo;
+pub use limit::{CachePreference, Limit, LimitError, SharedMemBankSize};
 pub use memory::{
     memcpy_d2d, memcpy_d2h, memcpy_h2d, DevByte, DevMem, DevMemSpore, HostMem, HostMemSpore,
 };
+pub use scheduler::{Region, Scheduler};
 pub use stream::{Stream, StreamSpore};
+pub use stream_pool::StreamPool;
 
 use std::{
     cmp::Ordering,
@@ -111,7 +148,7 @@ impl PartialOrd for Version {
 impl Ord for Version {
     #[inline]
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.major.cmp(&self.major) {
+        match self.major.cmp(&other.major) {
             Ordering::Equal => self.minor.cmp(&other.minor),
             other => other,
         }
@@ -166,23 +203,109 @@ impl From<usize> for MemSize {
 
 #[allow(unused_variables, non_snake_case)]
 #[inline(always)]
-pub fn init() -> Result<(), NoDevice> {
-    use bindings::{mcError_t::*, mcInit};
+pub fn init() -> Result<(), InitError> {
+    use bindings::{mcError_t, mcInit};
     match unsafe { mcInit(0) } {
-        mcSuccess => Ok(()),
-        mcErrorInvalidDevice => Err(NoDevice),
+        mcError_t::mcSuccess => match check_driver_version() {
+            Ok(()) => {
+                log::info!("MACA driver {}", driver_version());
+                Ok(())
+            }
+            Err(e) => {
+                log::error!(
+                    "MACA driver {} is older than toolkit {}",
+                    e.driver,
+                    e.toolkit
+                );
+                Err(InitError::DriverTooOld(e))
+            }
+        },
+        mcError_t::mcErrorInvalidDevice => Err(NoDevice),
         e => panic!("Failed to initialize MUSA: {e:?}"),
     }
 }
 
+/// 已安装驱动支持的最高版本。
+pub fn driver_version() -> Version {
+    let mut version = 0;
+    mxdrv!(mcDriverGetVersion(&mut version));
+    decode_version(version)
+}
+
+/// 运行时实际加载的运行时库版本。
+pub fn runtime_version() -> Version {
+    let mut version = 0;
+    mxdrv!(mcRuntimeGetVersion(&mut version));
+    decode_version(version)
+}
+
+/// 构建时使用的工具链版本，构建时未能识别版本则为 `None`。
+pub fn toolkit_version() -> Option<Version> {
+    let (major, minor) = option_env!("MACA_BUILD_VERSION")?.split_once('.')?;
+    Some(Version {
+        major: major.parse().ok()?,
+        minor: minor.parse().ok()?,
+    })
+}
+
+/// 检查已安装的驱动是否不低于构建时使用的工具链版本，工具链版本未知时视为通过。
+pub fn check_driver_version() -> Result<(), DriverTooOld> {
+    let Some(toolkit) = toolkit_version() else {
+        return Ok(());
+    };
+    let driver = driver_version();
+    if driver < toolkit {
+        Err(DriverTooOld { driver, toolkit })
+    } else {
+        Ok(())
+    }
+}
+
+/// 版本号编码为 `major * 1000 + minor * 10`。
+#[inline]
+fn decode_version(version: c_int) -> Version {
+    Version {
+        major: version / 1000,
+        minor: version % 1000 / 10,
+    }
+}
+
+#[deprecated = "use `Device::count` instead"]
+#[inline]
 pub fn get_device_count() -> i32 {
-    let mut a = 0;
-    mxdrv!(mcGetDeviceCount(&mut a));
-    a
+    Device::count() as _
 }
 
 #[test]
 fn test_binding() {
     let _ = init();
-    println!("{}", get_device_count());
+    println!("{}", Device::count());
+}
+
+#[test]
+fn test_version() {
+    assert_eq!(decode_version(2020), Version { major: 2, minor: 2 });
+    assert_eq!(
+        decode_version(12040),
+        Version {
+            major: 12,
+            minor: 4
+        }
+    );
+    if init().is_ok() {
+        println!(
+            "driver {} runtime {} toolkit {:?}",
+            driver_version(),
+            runtime_version(),
+            toolkit_version()
+        );
+    }
+}
+
+#[test]
+fn test_version_ord() {
+    let v = |major, minor| Version { major, minor };
+    assert!(v(8, 0) > v(7, 5));
+    assert!(v(7, 5) > v(7, 0));
+    assert_eq!(v(7, 5).cmp(&v(7, 5)), Ordering::Equal);
 }
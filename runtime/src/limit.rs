@@ -0,0 +1,210 @@
+use crate::{
+    bindings::{mcFuncCache_t, mcLimit_t, mcSharedMemConfig},
+    CurrentCtx, MemSize,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Limit {
+    /// 每个线程的栈大小。
+    StackSize,
+    /// 设备端 `printf` 的缓冲区大小。
+    PrintfFifoSize,
+    /// 设备端 `malloc` 的堆大小。
+    MallocHeapSize,
+}
+
+impl From<Limit> for mcLimit_t {
+    #[inline]
+    fn from(limit: Limit) -> Self {
+        match limit {
+            Limit::StackSize => Self::mcLimitStackSize,
+            Limit::PrintfFifoSize => Self::mcLimitPrintfFifoSize,
+            Limit::MallocHeapSize => Self::mcLimitMallocHeapSize,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LimitError {
+    /// 请求值超过了按设备规格推算的上限。
+    ExceedsDevice {
+        limit: Limit,
+        requested: usize,
+        max: usize,
+    },
+    /// 驱动拒绝了请求值。
+    Rejected { limit: Limit, requested: usize },
+}
+
+impl Limit {
+    /// 按设备显存容量和可同时驻留的线程数推算的上限。
+    ///
+    /// 栈大小按每个驻留线程平分显存，缓冲区和堆不超过显存容量。
+    fn device_max(self, total_memory: usize, resident_threads: usize) -> usize {
+        match self {
+            Self::StackSize => total_memory / resident_threads.max(1),
+            Self::PrintfFifoSize | Self::MallocHeapSize => total_memory,
+        }
+    }
+}
+
+/// L1 缓存与共享存储的划分偏好。
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub enum CachePreference {
+    #[default]
+    None,
+    Shared,
+    L1,
+    Equal,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub enum SharedMemBankSize {
+    #[default]
+    Default,
+    FourByte,
+    EightByte,
+}
+
+impl CurrentCtx {
+    pub fn limit(&self, limit: Limit) -> usize {
+        let mut value = 0;
+        mxdrv!(mcCtxGetLimit(&mut value, limit.into()));
+        value
+    }
+
+    /// 设置上下文限制，请求值超过设备上限或驱动不接受时返回错误。
+    pub fn set_limit(&self, limit: Limit, value: usize) -> Result<(), LimitError> {
+        use crate::bindings::{mcCtxSetLimit, mcError_t};
+        let max = self.max_limit(limit);
+        if value > max {
+            return Err(LimitError::ExceedsDevice {
+                limit,
+                requested: value,
+                max,
+            });
+        }
+        match unsafe { mcCtxSetLimit(limit.into(), value) } {
+            mcError_t::mcSuccess => Ok(()),
+            mcError_t::mcErrorInvalidValue
+            | mcError_t::mcErrorMemoryAllocation
+            | mcError_t::mcErrorNotSupported => Err(LimitError::Rejected {
+                limit,
+                requested: value,
+            }),
+            e => panic!("Failed to set limit {limit:?}: {e:?}"),
+        }
+    }
+
+    /// 按当前设备规格推算的限制上限，驱动可能拒绝更小的值。
+    pub fn max_limit(&self, limit: Limit) -> usize {
+        let dev = self.dev();
+        let threads = dev.sm_count() * dev.sm_limit().max_threads;
+        limit.device_max(dev.total_memory().0, threads)
+    }
+
+    #[inline]
+    pub fn stack_size(&self) -> MemSize {
+        self.limit(Limit::StackSize).into()
+    }
+
+    #[inline]
+    pub fn set_stack_size(&self, size: MemSize) -> Result<(), LimitError> {
+        self.set_limit(Limit::StackSize, size.0)
+    }
+
+    #[inline]
+    pub fn printf_fifo_size(&self) -> MemSize {
+        self.limit(Limit::PrintfFifoSize).into()
+    }
+
+    #[inline]
+    pub fn set_printf_fifo_size(&self, size: MemSize) -> Result<(), LimitError> {
+        self.set_limit(Limit::PrintfFifoSize, size.0)
+    }
+
+    #[inline]
+    pub fn malloc_heap_size(&self) -> MemSize {
+        self.limit(Limit::MallocHeapSize).into()
+    }
+
+    #[inline]
+    pub fn set_malloc_heap_size(&self, size: MemSize) -> Result<(), LimitError> {
+        self.set_limit(Limit::MallocHeapSize, size.0)
+    }
+
+    pub fn cache_preference(&self) -> CachePreference {
+        let mut config = mcFuncCache_t::mcFuncCachePreferNone;
+        mxdrv!(mcCtxGetCacheConfig(&mut config));
+        match config {
+            mcFuncCache_t::mcFuncCachePreferShared => CachePreference::Shared,
+            mcFuncCache_t::mcFuncCachePreferL1 => CachePreference::L1,
+            mcFuncCache_t::mcFuncCachePreferEqual => CachePreference::Equal,
+            _ => CachePreference::None,
+        }
+    }
+
+    pub fn set_cache_preference(&self, preference: CachePreference) {
+        let config = match preference {
+            CachePreference::None => mcFuncCache_t::mcFuncCachePreferNone,
+            CachePreference::Shared => mcFuncCache_t::mcFuncCachePreferShared,
+            CachePreference::L1 => mcFuncCache_t::mcFuncCachePreferL1,
+            CachePreference::Equal => mcFuncCache_t::mcFuncCachePreferEqual,
+        };
+        mxdrv!(mcCtxSetCacheConfig(config));
+    }
+
+    pub fn shared_mem_bank_size(&self) -> SharedMemBankSize {
+        let mut config = mcSharedMemConfig::mcSharedMemBankSizeDefault;
+        mxdrv!(mcCtxGetSharedMemConfig(&mut config));
+        match config {
+            mcSharedMemConfig::mcSharedMemBankSizeFourByte => SharedMemBankSize::FourByte,
+            mcSharedMemConfig::mcSharedMemBankSizeEightByte => SharedMemBankSize::EightByte,
+            _ => SharedMemBankSize::Default,
+        }
+    }
+
+    pub fn set_shared_mem_bank_size(&self, size: SharedMemBankSize) {
+        let config = match size {
+            SharedMemBankSize::Default => mcSharedMemConfig::mcSharedMemBankSizeDefault,
+            SharedMemBankSize::FourByte => mcSharedMemConfig::mcSharedMemBankSizeFourByte,
+            SharedMemBankSize::EightByte => mcSharedMemConfig::mcSharedMemBankSizeEightByte,
+        };
+        mxdrv!(mcCtxSetSharedMemConfig(config));
+    }
+}
+
+#[test]
+fn test_device_max() {
+    assert_eq!(Limit::StackSize.device_max(64 << 30, 1 << 16), 1 << 20);
+    assert_eq!(Limit::StackSize.device_max(64 << 30, 0), 64 << 30);
+    assert_eq!(
+        Limit::MallocHeapSize.device_max(64 << 30, 1 << 16),
+        64 << 30
+    );
+}
+
+#[test]
+fn test_limit() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    crate::Device::new(0).context().apply(|ctx| {
+        let heap = MemSize(8 << 20);
+        ctx.set_malloc_heap_size(heap).unwrap();
+        assert_eq!(ctx.malloc_heap_size(), heap);
+
+        let max = ctx.max_limit(Limit::StackSize);
+        assert_eq!(
+            ctx.set_limit(Limit::StackSize, max + 1),
+            Err(LimitError::ExceedsDevice {
+                limit: Limit::StackSize,
+                requested: max + 1,
+                max,
+            })
+        );
+
+        ctx.set_cache_preference(CachePreference::Shared);
+        println!("cache preference = {:?}", ctx.cache_preference());
+    });
+}
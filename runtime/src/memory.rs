@@ -1,4 +1,4 @@
-use crate::{bindings::mcDeviceptr_t, Blob, CurrentCtx, Stream};
+use crate::{bindings::mcDeviceptr_t, Blob, CurrentCtx, MemSize, Stream};
 use context_spore::{impl_spore, AsRaw};
 use std::{
     alloc::Layout,
@@ -81,6 +81,15 @@ impl CurrentCtx {
         mxdrv!(mcMemcpyHtoD(ptr, src, len));
         DevMem(unsafe { self.wrap_raw(Blob { ptr, len }) }, PhantomData)
     }
+
+    /// 返回设备上的空闲存储和总存储。
+    #[inline]
+    pub fn mem_info(&self) -> (MemSize, MemSize) {
+        let mut free = 0;
+        let mut total = 0;
+        mxdrv!(mcMemGetInfo(&mut free, &mut total));
+        (free.into(), total.into())
+    }
 }
 
 impl DevMem<'_> {
@@ -0,0 +1,269 @@
+use crate::{CurrentCtx, DevByte, Event, Stream};
+use std::mem::take;
+
+/// 任务读写的一段设备存储区域，以地址范围标识。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Region {
+    start: usize,
+    end: usize,
+}
+
+impl From<&[DevByte]> for Region {
+    #[inline]
+    fn from(value: &[DevByte]) -> Self {
+        let range = value.as_ptr_range();
+        Self {
+            start: range.start as _,
+            end: range.end as _,
+        }
+    }
+}
+
+impl Region {
+    #[inline]
+    fn overlaps(&self, other: &Self) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    #[inline]
+    fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+/// 跨流依赖调度器。
+///
+/// 任务声明自己读写的区域，调度器据此推导任务间的依赖，将任务分配到流池中，
+/// 并只在跨流依赖尚未被已有同步覆盖时插入事件等待。事件在所有流都已知其完成后回收复用。
+pub struct Scheduler<'ctx> {
+    streams: Vec<Stream<'ctx>>,
+    events: Vec<Option<Event<'ctx>>>,
+    pool: Vec<Event<'ctx>>,
+    planner: Planner,
+}
+
+impl<'ctx> Scheduler<'ctx> {
+    pub fn new(ctx: &'ctx CurrentCtx, streams: usize) -> Self {
+        assert!(streams > 0);
+        Self {
+            streams: (0..streams).map(|_| ctx.stream()).collect(),
+            events: Vec::new(),
+            pool: Vec::new(),
+            planner: Planner::new(streams),
+        }
+    }
+
+    #[inline]
+    pub fn streams(&self) -> &[Stream<'ctx>] {
+        &self.streams
+    }
+
+    /// 提交一个任务，返回它被分配到的流序号。
+    ///
+    /// `f` 只能在传入的流上提交读 `reads`、写 `writes` 的操作。
+    pub fn submit(
+        &mut self,
+        reads: &[Region],
+        writes: &[Region],
+        f: impl FnOnce(&Stream<'ctx>),
+    ) -> usize {
+        let Placement { stream, waits } = self.planner.plan(reads, writes);
+        let s = &self.streams[stream];
+        for task in waits {
+            s.wait_for(self.events[task].as_ref().unwrap());
+        }
+        f(s);
+
+        let event = match self.pool.pop() {
+            Some(mut event) => {
+                s.rerecord(&mut event);
+                event
+            }
+            None => s.record(),
+        };
+        self.events.push(Some(event));
+        for task in self.planner.settled() {
+            self.pool.extend(self.events[task].take());
+        }
+        stream
+    }
+
+    /// 同步所有流，此后提交的任务不再依赖之前的任何任务。
+    pub fn synchronize(&mut self) {
+        for s in &self.streams {
+            s.synchronize();
+        }
+        self.pool.extend(self.events.drain(..).flatten());
+        self.planner = Planner::new(self.streams.len());
+    }
+}
+
+struct Placement {
+    stream: usize,
+    waits: Vec<usize>,
+}
+
+/// 与设备无关的调度逻辑。
+///
+/// 每个流维护一个向量时钟，`clocks[s][t]` 表示流 `s` 上新提交的任务已确保在流 `t` 的前若干个任务之后执行。
+struct Planner {
+    next: usize,
+    clocks: Vec<Vec<usize>>,
+    tasks: Vec<Task>,
+    accesses: Vec<Access>,
+    live: Vec<usize>,
+}
+
+struct Task {
+    stream: usize,
+    seq: usize,
+    clock: Vec<usize>,
+}
+
+struct Access {
+    region: Region,
+    task: usize,
+    write: bool,
+}
+
+impl Planner {
+    fn new(streams: usize) -> Self {
+        Self {
+            next: 0,
+            clocks: vec![vec![0; streams]; streams],
+            tasks: Vec::new(),
+            accesses: Vec::new(),
+            live: Vec::new(),
+        }
+    }
+
+    fn plan(&mut self, reads: &[Region], writes: &[Region]) -> Placement {
+        // 读依赖之前的写，写依赖之前的读和写
+        let mut deps = self
+            .accesses
+            .iter()
+            .filter(|a| {
+                writes.iter().any(|r| r.overlaps(&a.region))
+                    || (a.write && reads.iter().any(|r| r.overlaps(&a.region)))
+            })
+            .map(|a| a.task)
+            .collect::<Vec<_>>();
+        deps.sort_unstable();
+        deps.dedup();
+
+        // 无依赖的任务轮流分配，否则跟随最近的依赖以省去一次等待
+        let stream = match deps.last() {
+            Some(&task) => self.tasks[task].stream,
+            None => {
+                let s = self.next;
+                self.next = (self.next + 1) % self.clocks.len();
+                s
+            }
+        };
+
+        let mut waits = Vec::new();
+        for &task in deps.iter().rev() {
+            let Task {
+                stream: t,
+                seq,
+                ref clock,
+            } = self.tasks[task];
+            if self.clocks[stream][t] >= seq {
+                continue;
+            }
+            waits.push(task);
+            for (mine, theirs) in self.clocks[stream].iter_mut().zip(clock) {
+                *mine = (*mine).max(*theirs);
+            }
+        }
+
+        let id = self.tasks.len();
+        self.clocks[stream][stream] += 1;
+        self.tasks.push(Task {
+            stream,
+            seq: self.clocks[stream][stream],
+            clock: self.clocks[stream].clone(),
+        });
+        self.live.push(id);
+
+        // 被新的写完全覆盖的访问不会再产生依赖
+        self.accesses
+            .retain(|a| !writes.iter().any(|w| w.contains(&a.region)));
+        self.accesses.extend(reads.iter().map(|r| Access {
+            region: *r,
+            task: id,
+            write: false,
+        }));
+        self.accesses.extend(writes.iter().map(|w| Access {
+            region: *w,
+            task: id,
+            write: true,
+        }));
+
+        Placement { stream, waits }
+    }
+
+    /// 取出所有流都已知其完成的任务，这些任务的事件不会再被等待。
+    fn settled(&mut self) -> Vec<usize> {
+        let (settled, live) = take(&mut self.live).into_iter().partition(|&task| {
+            let Task { stream, seq, .. } = self.tasks[task];
+            self.clocks.iter().all(|clock| clock[stream] >= seq)
+        });
+        self.live = live;
+        settled
+    }
+}
+
+#[test]
+fn test_plan() {
+    let region = |start, end| Region { start, end };
+    let mut planner = Planner::new(2);
+
+    // 两个独立的写入分配到不同的流
+    let a = planner.plan(&[], &[region(0, 16)]);
+    let b = planner.plan(&[], &[region(16, 32)]);
+    assert_ne!(a.stream, b.stream);
+    assert!(a.waits.is_empty() && b.waits.is_empty());
+
+    // 读两者的任务只需等待另一个流
+    let c = planner.plan(&[region(0, 32)], &[region(32, 48)]);
+    assert_eq!(c.stream, b.stream);
+    assert_eq!(c.waits, [0]);
+
+    // 已经同步过的依赖不再等待
+    let d = planner.plan(&[region(0, 8)], &[region(48, 64)]);
+    assert_eq!(d.stream, a.stream);
+    assert!(d.waits.is_empty());
+    let e = planner.plan(&[region(32, 64)], &[]);
+    assert_eq!(e.stream, d.stream);
+    assert_eq!(e.waits, [2]);
+
+    // 任务 0 已被两个流知晓
+    assert!(planner.settled().contains(&0));
+}
+
+#[test]
+fn test_scheduler() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    crate::Device::new(0).context().apply(|ctx| {
+        let data = [1u32; 64];
+        let mut a = ctx.malloc::<u32>(64);
+        let mut b = ctx.malloc::<u32>(64);
+        let mut c = ctx.malloc::<u32>(64);
+
+        let mut scheduler = Scheduler::new(ctx, 2);
+        let a_ = Region::from(&*a);
+        scheduler.submit(&[], &[a_], |s| s.memcpy_h2d(&mut a, &data));
+        let b_ = Region::from(&*b);
+        scheduler.submit(&[a_], &[b_], |s| s.memcpy_d2d(&mut b, &a));
+        let c_ = Region::from(&*c);
+        scheduler.submit(&[b_], &[c_], |s| s.memcpy_d2d(&mut c, &b));
+        scheduler.synchronize();
+
+        let mut host = [0u32; 64];
+        crate::memcpy_d2h(&mut host, &c);
+        assert_eq!(host, data);
+    });
+}
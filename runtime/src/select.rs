@@ -0,0 +1,92 @@
+use crate::{Device, MemSize, Version};
+
+impl Device {
+    /// 查询设备上的空闲存储。
+    ///
+    /// 在临时创建的上下文上查询，不影响设备的主上下文。
+    /// 临时上下文本身占用的存储也计为已用，结果略小于实际空闲量。
+    pub fn free_memory(&self) -> MemSize {
+        self.context().apply(|ctx| ctx.mem_info().0)
+    }
+
+    /// 选出计算能力不低于 `min` 的可见设备。
+    pub fn select_by_compute_capability(min: Version) -> Vec<Self> {
+        Self::visible()
+            .into_iter()
+            .filter(|dev| dev.compute_capability() >= min)
+            .collect()
+    }
+
+    /// 选出名字匹配 `pattern` 的可见设备。
+    ///
+    /// 模式中 `*` 匹配任意个字符，`?` 匹配一个字符，不区分大小写。
+    pub fn select_by_name(pattern: &str) -> Vec<Self> {
+        Self::visible()
+            .into_iter()
+            .filter(|dev| glob_match(pattern, &dev.name()))
+            .collect()
+    }
+
+    /// 选出空闲存储最多的可见设备。
+    pub fn select_most_free_memory() -> Option<Self> {
+        Self::visible()
+            .into_iter()
+            .map(|dev| (dev.free_memory().0, dev))
+            .max_by_key(|(free, _)| *free)
+            .map(|(_, dev)| dev)
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let t = text.to_lowercase().chars().collect::<Vec<_>>();
+    // 贪心匹配，遇到失配时回溯到最近的 `*`
+    let (mut i, mut j) = (0, 0);
+    let mut star = None;
+    while j < t.len() {
+        match p.get(i) {
+            Some('*') => {
+                star = Some((i, j));
+                i += 1;
+            }
+            Some(&c) if c == '?' || c == t[j] => {
+                i += 1;
+                j += 1;
+            }
+            _ => match star {
+                Some((si, sj)) => {
+                    i = si + 1;
+                    j = sj + 1;
+                    star = Some((si, sj + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    p[i..].iter().all(|&c| c == '*')
+}
+
+#[test]
+fn test_glob() {
+    assert!(glob_match("MXC*", "MXC500"));
+    assert!(glob_match("*c5?0", "MXC500"));
+    assert!(glob_match("*", ""));
+    assert!(!glob_match("MXC?", "MXC500"));
+    assert!(!glob_match("N*", "MXC500"));
+}
+
+#[test]
+fn test_select() {
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    let all = Device::select_by_name("*");
+    assert_eq!(all.len(), Device::visible().len());
+    assert_eq!(
+        Device::select_by_compute_capability(Version { major: 0, minor: 0 }).len(),
+        all.len()
+    );
+    if let Some(dev) = Device::select_most_free_memory() {
+        println!("{} {}", dev.name(), dev.free_memory());
+    }
+}
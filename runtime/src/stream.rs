@@ -1,6 +1,6 @@
 use crate::{bindings::mcStream_t, CurrentCtx};
 use context_spore::{impl_spore, AsRaw};
-use std::{marker::PhantomData, ptr::null_mut};
+use std::{ffi::c_int, marker::PhantomData, ops::RangeInclusive, ptr::null_mut};
 
 impl_spore!(Stream and StreamSpore by (CurrentCtx, mcStream_t));
 
@@ -11,6 +11,24 @@ impl CurrentCtx {
         mxdrv!(mcStreamCreate(&mut stream));
         Stream(unsafe { self.wrap_raw(stream) }, PhantomData)
     }
+
+    /// 创建指定优先级的流，数值越小优先级越高。
+    #[inline]
+    pub fn stream_with_priority(&self, priority: c_int) -> Stream<'_> {
+        assert!(self.stream_priorities().contains(&priority));
+        let mut stream = null_mut();
+        mxdrv!(mcStreamCreateWithPriority(&mut stream, 0, priority));
+        Stream(unsafe { self.wrap_raw(stream) }, PhantomData)
+    }
+
+    /// 流优先级的取值范围，从最高优先级到最低优先级。
+    #[inline]
+    pub fn stream_priorities(&self) -> RangeInclusive<c_int> {
+        let mut least = 0;
+        let mut greatest = 0;
+        mxdrv!(mcDeviceGetStreamPriorityRange(&mut least, &mut greatest));
+        greatest..=least
+    }
 }
 
 impl Drop for Stream<'_> {
@@ -34,4 +52,11 @@ impl Stream<'_> {
     pub fn synchronize(&self) {
         mxdrv!(mcStreamSynchronize(self.0.rss));
     }
+
+    #[inline]
+    pub fn priority(&self) -> c_int {
+        let mut priority = 0;
+        mxdrv!(mcStreamGetPriority(self.0.rss, &mut priority));
+        priority
+    }
 }
@@ -0,0 +1,130 @@
+use crate::{Context, StreamSpore};
+use context_spore::{ContextResource, ContextSpore};
+use std::{
+    ffi::c_int,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicUsize, Ordering::Relaxed},
+        OnceLock,
+    },
+};
+
+/// 绑定到一个上下文的流池。
+///
+/// 每个优先级的流在第一次被请求时一次性创建，以孢子的形式借出，可以在任何线程中在该上下文上复原使用。
+pub struct StreamPool<'a> {
+    ctx: &'a Context,
+    size: usize,
+    priorities: RangeInclusive<c_int>,
+    slots: Box<[Slot]>,
+}
+
+#[derive(Default)]
+struct Slot {
+    streams: OnceLock<Box<[StreamSpore]>>,
+    next: AtomicUsize,
+}
+
+impl<'a> StreamPool<'a> {
+    pub fn new(ctx: &'a Context, size: usize) -> Self {
+        assert!(size > 0);
+        let priorities = ctx.apply(|ctx| ctx.stream_priorities());
+        let len = (priorities.end() - priorities.start() + 1) as usize;
+        Self {
+            ctx,
+            size,
+            priorities,
+            slots: (0..len).map(|_| Slot::default()).collect(),
+        }
+    }
+
+    #[inline]
+    pub fn context(&self) -> &'a Context {
+        self.ctx
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// 可用的优先级，从最高优先级到最低优先级。
+    #[inline]
+    pub fn priorities(&self) -> RangeInclusive<c_int> {
+        self.priorities.clone()
+    }
+
+    /// 轮流借出指定优先级的流。
+    pub fn next(&self, priority: c_int) -> &StreamSpore {
+        let slot = self.slot(priority);
+        let i = slot.next.fetch_add(1, Relaxed);
+        &self.streams(priority, slot)[i % self.size]
+    }
+
+    /// 借出指定优先级上与当前线程绑定的流，同一线程总是得到同一个流。
+    pub fn for_thread(&self, priority: c_int) -> &StreamSpore {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        thread_local! {
+            static INDEX: usize = NEXT.fetch_add(1, Relaxed);
+        }
+        let slot = self.slot(priority);
+        &self.streams(priority, slot)[INDEX.with(|i| *i) % self.size]
+    }
+
+    fn slot(&self, priority: c_int) -> &Slot {
+        assert!(self.priorities.contains(&priority));
+        &self.slots[(priority - self.priorities.start()) as usize]
+    }
+
+    fn streams<'s>(&'s self, priority: c_int, slot: &'s Slot) -> &'s [StreamSpore] {
+        slot.streams.get_or_init(|| {
+            self.ctx.apply(|ctx| {
+                (0..self.size)
+                    .map(|_| ctx.stream_with_priority(priority).sporulate())
+                    .collect()
+            })
+        })
+    }
+}
+
+impl Drop for StreamPool<'_> {
+    fn drop(&mut self) {
+        self.ctx.apply(|ctx| {
+            for slot in self.slots.iter_mut() {
+                for stream in slot.streams.take().into_iter().flat_map(Vec::from) {
+                    drop(stream.sprout(ctx));
+                }
+            }
+        });
+    }
+}
+
+#[test]
+fn test_pool() {
+    use std::ptr::eq;
+
+    if let Err(crate::NoDevice) = crate::init() {
+        return;
+    }
+    let ctx = crate::Device::new(0).context();
+    let pool = StreamPool::new(&ctx, 2);
+    let priority = *pool.priorities().end();
+
+    let a = pool.next(priority);
+    let b = pool.next(priority);
+    assert!(!eq(a, b));
+    assert!(eq(a, pool.next(priority)));
+    assert!(eq(pool.for_thread(priority), pool.for_thread(priority)));
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                ctx.apply(|ctx| {
+                    let stream = pool.for_thread(priority).sprout_ref(ctx);
+                    assert_eq!(stream.priority(), priority);
+                    stream.synchronize();
+                })
+            });
+        }
+    });
+}
@@ -0,0 +1,81 @@
+//! Regenerates checked-in bindings from the installed toolkit and reports what changed.
+//!
+//! ```text
+//! regen-bindings [--newtype-enums] <header> <allowlist> <error type> <checked-in file> [--write]
+//! ```
+//!
+//! Without `--write` only the difference is printed.
+//! `--newtype-enums` generates enums as newtypes over integers instead of Rust enums.
+
+use search_mx_tools::{bindgen, MxToolkit};
+use std::{collections::BTreeSet, env, fs, process::exit};
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let write = args.iter().any(|a| a == "--write");
+    let newtype_enums = args.iter().any(|a| a == "--newtype-enums");
+    let args = args
+        .iter()
+        .filter(|a| !a.starts_with("--"))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let [header, allowlist, error_type, file] = args[..] else {
+        eprintln!(
+            "usage: regen-bindings [--newtype-enums] <header> <allowlist> <error type> <checked-in file> [--write]"
+        );
+        exit(2);
+    };
+
+    let Some(mx) = MxToolkit::find() else {
+        eprintln!("MACA toolkit not found");
+        exit(1);
+    };
+    let version = mx
+        .version()
+        .map_or_else(|| "unknown".into(), |v| v.to_string());
+    let mut builder = mx.bindgen_standalone(header, allowlist, error_type);
+    if newtype_enums {
+        builder = builder.default_enum_style(bindgen::EnumVariation::NewType {
+            is_bitfield: false,
+            is_global: false,
+        });
+    }
+    let bindings = builder
+        .generate()
+        .expect("Unable to generate bindings")
+        .to_string();
+    let new =
+        format!("// Generated from MACA {version} by regen-bindings, do not edit.\n\n{bindings}");
+
+    let old = fs::read_to_string(file).unwrap_or_default();
+    let (old_items, new_items) = (items(&old), items(&new));
+    for item in old_items.difference(&new_items) {
+        println!("- {item}");
+    }
+    for item in new_items.difference(&old_items) {
+        println!("+ {item}");
+    }
+    if old == new {
+        println!("{file} is up to date");
+    } else if write {
+        fs::write(file, new).unwrap();
+        println!("{file} updated to MACA {version}");
+    } else {
+        println!("{file} is outdated, rerun with --write to update");
+        exit(1);
+    }
+}
+
+/// Collects public item declarations such as `fn mcMalloc`.
+fn items(text: &str) -> BTreeSet<String> {
+    const KINDS: [&str; 6] = ["fn", "struct", "enum", "type", "const", "static"];
+    text.lines()
+        .filter_map(|line| {
+            let mut tokens = line.trim().strip_prefix("pub ")?.split_whitespace();
+            let kind = tokens.next()?;
+            let name = tokens.next()?;
+            let name = name.split(['(', '<', ':', ';']).next()?;
+            KINDS.contains(&kind).then(|| format!("{kind} {name}"))
+        })
+        .collect()
+}
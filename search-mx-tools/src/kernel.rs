@@ -0,0 +1,314 @@
+use crate::MxToolkit;
+use std::{
+    env::var_os,
+    ffi::OsString,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// Builder that compiles MACA kernel sources from a build script, in the style of `cc::Build`.
+///
+/// ```no_run
+/// search_mx_tools::KernelBuild::new()
+///     .file("src/kernels/add.maca")
+///     .arch("xcore1000")
+///     .include("src/kernels")
+///     .define("BLOCK_SIZE", Some("256"))
+///     .compile("kernels")
+///     .unwrap();
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct KernelBuild {
+    toolkit: Option<MxToolkit>,
+    files: Vec<PathBuf>,
+    archs: Vec<String>,
+    includes: Vec<PathBuf>,
+    defines: Vec<(String, Option<String>)>,
+    flags: Vec<String>,
+    out_dir: Option<PathBuf>,
+    jobs: Option<usize>,
+}
+
+/// Error raised while compiling kernel sources.
+#[derive(Debug)]
+pub enum KernelBuildError {
+    /// No valid toolkit was found.
+    ToolkitNotFound,
+    /// No output directory was given and `OUT_DIR` is not set.
+    NoOutDir,
+    /// Failed to spawn a tool or to access a file.
+    Io(io::Error),
+    /// A tool exited with failure.
+    Command {
+        /// The command line that failed.
+        command: String,
+        /// Captured standard error of the tool.
+        stderr: String,
+    },
+}
+
+impl fmt::Display for KernelBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ToolkitNotFound => write!(f, "MACA toolkit not found"),
+            Self::NoOutDir => write!(f, "output directory is not set"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Command { command, stderr } => write!(f, "`{command}` failed:\n{stderr}"),
+        }
+    }
+}
+
+impl std::error::Error for KernelBuildError {}
+
+impl From<io::Error> for KernelBuildError {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl KernelBuild {
+    /// Creates an empty builder.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses `toolkit` instead of searching with [`MxToolkit::find`].
+    pub fn toolkit(mut self, toolkit: MxToolkit) -> Self {
+        self.toolkit = Some(toolkit);
+        self
+    }
+
+    /// Adds a source file.
+    pub fn file(mut self, path: impl AsRef<Path>) -> Self {
+        self.files.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds several source files.
+    pub fn files<P: AsRef<Path>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        self.files
+            .extend(paths.into_iter().map(|p| p.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Adds a target architecture; the output contains code for every added one.
+    pub fn arch(mut self, arch: impl Into<String>) -> Self {
+        self.archs.push(arch.into());
+        self
+    }
+
+    /// Adds an include directory.
+    pub fn include(mut self, dir: impl AsRef<Path>) -> Self {
+        self.includes.push(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Defines a preprocessor macro, optionally with a value.
+    pub fn define(mut self, name: &str, value: Option<&str>) -> Self {
+        self.defines.push((name.into(), value.map(Into::into)));
+        self
+    }
+
+    /// Passes an extra flag to the compiler.
+    pub fn flag(mut self, flag: impl Into<String>) -> Self {
+        self.flags.push(flag.into());
+        self
+    }
+
+    /// Sets the output directory, `OUT_DIR` by default.
+    pub fn out_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.out_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets the number of parallel compiler processes,
+    /// `NUM_JOBS` or the available parallelism by default.
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = Some(jobs.max(1));
+        self
+    }
+
+    /// Compiles every source into a fat binary and returns their paths, in source order.
+    pub fn compile_fatbin(&self) -> Result<Vec<PathBuf>, KernelBuildError> {
+        self.compile_each("fatbin", &["--maca-device-only"])
+    }
+
+    /// Compiles every source into an object and archives them into `lib{name}.a`,
+    /// then tells cargo to link it statically.
+    pub fn compile(&self, name: &str) -> Result<PathBuf, KernelBuildError> {
+        let objects = self.compile_each("o", &["-fPIC", "-c"])?;
+        let out_dir = self.output_dir()?;
+        let lib = out_dir.join(format!("lib{name}.a"));
+        if lib.exists() {
+            fs::remove_file(&lib)?;
+        }
+        let ar = var_os("AR").unwrap_or_else(|| "ar".into());
+        let mut cmd = Command::new(ar);
+        cmd.arg("crs").arg(&lib).args(&objects);
+        run(&mut cmd)?;
+
+        println!("cargo:rustc-link-search=native={}", out_dir.display());
+        println!("cargo:rustc-link-lib=static={name}");
+        Ok(lib)
+    }
+
+    fn output_dir(&self) -> Result<PathBuf, KernelBuildError> {
+        self.out_dir
+            .clone()
+            .or_else(|| var_os("OUT_DIR").map(PathBuf::from))
+            .ok_or(KernelBuildError::NoOutDir)
+    }
+
+    fn compile_each(&self, ext: &str, mode: &[&str]) -> Result<Vec<PathBuf>, KernelBuildError> {
+        let toolkit = match &self.toolkit {
+            Some(toolkit) => toolkit.clone(),
+            None => MxToolkit::find().ok_or(KernelBuildError::ToolkitNotFound)?,
+        };
+        let out_dir = self.output_dir()?;
+        fs::create_dir_all(&out_dir)?;
+
+        let jobs = self
+            .jobs
+            .or_else(|| var_os("NUM_JOBS")?.to_str()?.parse().ok())
+            .or_else(|| thread::available_parallelism().ok().map(Into::into))
+            .unwrap_or(1);
+
+        let outputs = self
+            .files
+            .iter()
+            .map(|src| out_dir.join(output_name(src, ext)))
+            .collect::<Vec<_>>();
+        let next = AtomicUsize::new(0);
+        let results = thread::scope(|s| {
+            let workers = (0..jobs.min(self.files.len()))
+                .map(|_| {
+                    s.spawn(|| loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(src) = self.files.get(i) else {
+                            return Ok(());
+                        };
+                        self.compile_one(&toolkit, src, &outputs[i], mode)?;
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .map(|w| w.join().unwrap())
+                .collect::<Vec<Result<(), KernelBuildError>>>()
+        });
+        results.into_iter().collect::<Result<(), _>>()?;
+        Ok(outputs)
+    }
+
+    fn compile_one(
+        &self,
+        toolkit: &MxToolkit,
+        src: &Path,
+        out: &Path,
+        mode: &[&str],
+    ) -> Result<(), KernelBuildError> {
+        let dep = out.with_extension("d");
+        let mut cmd = Command::new(toolkit.compiler());
+        cmd.args(["-x", "maca"])
+            .args(mode)
+            .args(self.archs.iter().map(|a| format!("--offload-arch={a}")))
+            .arg(format!("-I{}", toolkit.include.display()))
+            .args(self.includes.iter().map(|dir| {
+                let mut arg = OsString::from("-I");
+                arg.push(dir);
+                arg
+            }))
+            .args(self.defines.iter().map(|(name, value)| match value {
+                Some(value) => format!("-D{name}={value}"),
+                None => format!("-D{name}"),
+            }))
+            .args(&self.flags)
+            .arg("-MD")
+            .arg("-MF")
+            .arg(&dep)
+            .arg("-o")
+            .arg(out)
+            .arg(src);
+        run(&mut cmd)?;
+
+        println!("cargo:rerun-if-changed={}", src.display());
+        for path in parse_depfile(&fs::read_to_string(&dep)?) {
+            println!("cargo:rerun-if-changed={path}");
+        }
+        Ok(())
+    }
+}
+
+fn run(cmd: &mut Command) -> Result<(), KernelBuildError> {
+    let output = cmd.output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(KernelBuildError::Command {
+            command: format!("{cmd:?}"),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// Names outputs after the whole source path so `a/k.maca` and `b/k.maca` do not collide.
+fn output_name(src: &Path, ext: &str) -> String {
+    let stem = src
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    format!("{stem}.{ext}")
+}
+
+/// Lists the prerequisites in a make-style dependency file.
+fn parse_depfile(text: &str) -> Vec<String> {
+    let text = text.replace("\\\r\n", " ").replace("\\\n", " ");
+    let mut deps = Vec::new();
+    for line in text.lines() {
+        let Some((_, prerequisites)) = line.split_once(": ") else {
+            continue;
+        };
+        let mut current = String::new();
+        let mut chars = prerequisites.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => current.extend(chars.next()),
+                c if c.is_whitespace() => {
+                    if !current.is_empty() {
+                        deps.push(std::mem::take(&mut current));
+                    }
+                }
+                c => current.push(c),
+            }
+        }
+        if !current.is_empty() {
+            deps.push(current);
+        }
+    }
+    deps
+}
+
+#[test]
+fn test_parse_depfile() {
+    let deps = parse_depfile(
+        "out/add.o: src/add.maca \\\n  src/common\\ dir/util.h /opt/maca/include/mcr/mc_runtime.h\n",
+    );
+    assert_eq!(
+        deps,
+        [
+            "src/add.maca",
+            "src/common dir/util.h",
+            "/opt/maca/include/mcr/mc_runtime.h",
+        ]
+    );
+    assert_eq!(
+        output_name(Path::new("src/a/k.maca"), "o"),
+        "src_a_k_maca.o"
+    );
+}
@@ -1,10 +1,23 @@
 #![doc = include_str!("../README.md")]
 #![deny(warnings, unsafe_code, missing_docs)]
 
-use std::{env::var_os, path::PathBuf};
+mod kernel;
+mod library;
+mod toolkit;
+mod version;
 
-/// Returns the path to the mx home directory, if it is set.
+pub use kernel::{KernelBuild, KernelBuildError};
+pub use library::MxLibrary;
+pub use toolkit::{MxToolkit, DEFAULT_MX_HOME};
+pub use version::{InvalidVersion, MxVersion};
+
+#[cfg(feature = "bindgen")]
+pub use bindgen;
+
+use std::path::PathBuf;
+
+/// Returns the root directory of the first valid toolkit found by [`MxToolkit::find`].
 #[inline]
 pub fn find_mx_home() -> Option<PathBuf> {
-    var_os("MACA_PATH").map(PathBuf::from)
+    MxToolkit::find().map(|toolkit| toolkit.home)
 }
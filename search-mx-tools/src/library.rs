@@ -0,0 +1,104 @@
+use crate::MxToolkit;
+use std::fs::read_dir;
+
+/// Optional libraries shipped with the toolkit.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum MxLibrary {
+    /// Basic linear algebra.
+    Blas,
+    /// Deep neural network primitives.
+    Dnn,
+    /// Collective communication.
+    Ccl,
+    /// Fast Fourier transforms.
+    Fft,
+    /// Random number generation.
+    Rand,
+}
+
+impl MxLibrary {
+    /// All known libraries.
+    pub const ALL: [Self; 5] = [Self::Blas, Self::Dnn, Self::Ccl, Self::Fft, Self::Rand];
+
+    /// Name of the shared library, without the `lib` prefix and extension.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Blas => "mcblas",
+            Self::Dnn => "mcdnn",
+            Self::Ccl => "mccl",
+            Self::Fft => "mcfft",
+            Self::Rand => "mcrand",
+        }
+    }
+
+    /// Main header, relative to the include directory.
+    pub const fn header(self) -> &'static str {
+        match self {
+            Self::Blas => "mcblas/mcblas.h",
+            Self::Dnn => "mcdnn/mcdnn.h",
+            Self::Ccl => "mccl/mccl.h",
+            Self::Fft => "mcfft/mcfft.h",
+            Self::Rand => "mcrand/mcrand.h",
+        }
+    }
+}
+
+impl MxToolkit {
+    /// Checks that both the header and the shared library of `lib` are installed.
+    pub fn has_library(&self, lib: MxLibrary) -> bool {
+        if !self.include.join(lib.header()).is_file() {
+            return false;
+        }
+        let prefix = format!("lib{}.so", lib.name());
+        read_dir(&self.lib).is_ok_and(|dir| {
+            dir.flatten()
+                .any(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        })
+    }
+
+    /// Lists the installed optional libraries.
+    pub fn libraries(&self) -> Vec<MxLibrary> {
+        MxLibrary::ALL
+            .into_iter()
+            .filter(|&lib| self.has_library(lib))
+            .collect()
+    }
+
+    /// Tells cargo to link `lib` dynamically from the toolkit.
+    pub fn link(&self, lib: MxLibrary) {
+        println!("cargo:rustc-link-search=native={}", self.lib.display());
+        println!("cargo:rustc-link-lib=dylib={}", lib.name());
+    }
+}
+
+#[cfg(feature = "bindgen")]
+impl MxToolkit {
+    /// Creates a bindgen builder for `header` with the settings shared by all binding crates.
+    ///
+    /// Items matching `allowlist` are generated, enums are non-exhaustive Rust enums,
+    /// and `error_type` is marked `#[must_use]`.
+    /// The builder reports the parsed headers to cargo, so it is meant for build scripts.
+    pub fn bindgen(&self, header: &str, allowlist: &str, error_type: &str) -> bindgen::Builder {
+        self.bindgen_standalone(header, allowlist, error_type)
+            .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
+    }
+
+    /// Same as [`MxToolkit::bindgen`] but without the cargo callbacks,
+    /// for tools that run outside build scripts.
+    pub fn bindgen_standalone(
+        &self,
+        header: &str,
+        allowlist: &str,
+        error_type: &str,
+    ) -> bindgen::Builder {
+        bindgen::Builder::default()
+            .header(header)
+            .clang_arg(format!("-I{}", self.include.display()))
+            .allowlist_item(allowlist)
+            .must_use_type(error_type)
+            .default_enum_style(bindgen::EnumVariation::Rust {
+                non_exhaustive: true,
+            })
+            .use_core()
+    }
+}
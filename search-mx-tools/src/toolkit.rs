@@ -0,0 +1,117 @@
+use std::{
+    env::{split_paths, var_os},
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+/// Default installation directory of the MACA toolkit.
+pub const DEFAULT_MX_HOME: &str = "/opt/maca";
+
+const COMPILER: &str = "mxcc";
+const HEADER: &str = "include/mcr/mc_runtime.h";
+const LIBRARY: &str = "mcruntime";
+
+/// A validated MACA toolkit installation.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MxToolkit {
+    /// Root directory of the toolkit.
+    pub home: PathBuf,
+    /// Directory containing the runtime headers.
+    pub include: PathBuf,
+    /// Directory containing the runtime libraries.
+    pub lib: PathBuf,
+    /// Directory containing the compiler and other tools.
+    pub bin: PathBuf,
+}
+
+impl MxToolkit {
+    /// Searches for a toolkit in order:
+    ///
+    /// 1. the directory in `MACA_PATH`;
+    /// 2. the default directory `/opt/maca`;
+    /// 3. versioned directories `/opt/maca-x.y`, newest first;
+    /// 4. the parent of the directory containing `mxcc` on `PATH`.
+    ///
+    /// The first candidate with the expected headers and libraries is returned.
+    pub fn find() -> Option<Self> {
+        var_os("MACA_PATH")
+            .map(PathBuf::from)
+            .into_iter()
+            .chain([PathBuf::from(DEFAULT_MX_HOME)])
+            .chain(versioned_homes(Path::new("/opt")))
+            .chain(compiler_home())
+            .find_map(Self::from_home)
+    }
+
+    /// Validates `home` as a toolkit root.
+    pub fn from_home(home: impl Into<PathBuf>) -> Option<Self> {
+        let home = home.into();
+        if !home.join(HEADER).is_file() {
+            return None;
+        }
+        let lib = home.join("lib");
+        let has_lib = read_dir(&lib).ok()?.flatten().any(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with(&format!("lib{LIBRARY}.")) && !name.ends_with(".a")
+        });
+        if !has_lib {
+            return None;
+        }
+        Some(Self {
+            include: home.join("include"),
+            bin: home.join("bin"),
+            lib,
+            home,
+        })
+    }
+
+    /// Path to the `mxcc` compiler.
+    #[inline]
+    pub fn compiler(&self) -> PathBuf {
+        self.bin.join(COMPILER)
+    }
+}
+
+/// Lists `maca-x.y` directories under `root`, newest version first.
+fn versioned_homes(root: &Path) -> Vec<PathBuf> {
+    let Ok(dir) = read_dir(root) else {
+        return vec![];
+    };
+    let mut homes = dir
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let version = parse_version(name.to_str()?.strip_prefix("maca-")?)?;
+            Some((version, entry.path()))
+        })
+        .collect::<Vec<_>>();
+    homes.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+    homes.into_iter().map(|(_, path)| path).collect()
+}
+
+fn parse_version(s: &str) -> Option<Vec<u32>> {
+    s.split('.').map(|n| n.parse().ok()).collect()
+}
+
+/// Finds `mxcc` on `PATH` and returns the directory above its `bin`.
+fn compiler_home() -> Option<PathBuf> {
+    split_paths(&var_os("PATH")?)
+        .map(|dir| dir.join(COMPILER))
+        .find(|path| path.is_file())
+        .and_then(|path| path.canonicalize().ok())
+        .and_then(|path| Some(path.parent()?.parent()?.to_path_buf()))
+}
+
+#[test]
+fn test_versioned_homes() {
+    use std::fs::create_dir_all;
+
+    let root = std::env::temp_dir().join(format!("search-mx-tools-{}", std::process::id()));
+    for name in ["maca-2.9", "maca-2.20", "maca-x", "cuda-12.0"] {
+        create_dir_all(root.join(name)).unwrap();
+    }
+    let homes = versioned_homes(&root);
+    std::fs::remove_dir_all(&root).unwrap();
+    assert_eq!(homes, [root.join("maca-2.20"), root.join("maca-2.9")]);
+}
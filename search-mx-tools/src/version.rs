@@ -0,0 +1,178 @@
+use crate::MxToolkit;
+use std::{
+    fmt,
+    fs::{read_dir, read_to_string},
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// Files that may record the toolkit release, relative to the toolkit root.
+const VERSION_FILES: [&str; 2] = ["Version.txt", "version.txt"];
+
+/// Release of a MACA toolkit.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct MxVersion {
+    /// Major version.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Patch version, `0` if unknown.
+    pub patch: u32,
+}
+
+/// Error returned when a version string cannot be parsed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidVersion;
+
+impl MxVersion {
+    /// Creates a version with patch `0`.
+    #[inline]
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch: 0,
+        }
+    }
+
+    /// Name of the cfg flag for this release, such as `maca_2_20`.
+    #[inline]
+    pub fn cfg_name(&self) -> String {
+        format!("maca_{}_{}", self.major, self.minor)
+    }
+}
+
+impl fmt::Display for MxVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for MxVersion {
+    type Err = InvalidVersion;
+
+    /// Parses `x.y` or `x.y.z`; extra components are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('.').map(str::parse::<u32>);
+        let mut next = || parts.next().transpose().map_err(|_| InvalidVersion);
+        let major = next()?.ok_or(InvalidVersion)?;
+        let minor = next()?.ok_or(InvalidVersion)?;
+        let patch = next()?.unwrap_or(0);
+        Ok(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl MxToolkit {
+    /// Detects the toolkit release.
+    ///
+    /// The version file in the toolkit root is read first.
+    /// If it is missing, the `MACA_VERSION_*` macros in the runtime headers are used.
+    pub fn version(&self) -> Option<MxVersion> {
+        self.version_file()
+            .and_then(|path| read_to_string(path).ok())
+            .and_then(|text| parse_version_text(&text))
+            .or_else(|| self.header_version())
+    }
+
+    /// Prints `cargo:rerun-if-env-changed` for the variables that affect discovery.
+    ///
+    /// Build scripts should call this before [`MxToolkit::find`],
+    /// so that installing a toolkit later triggers a rebuild.
+    pub fn emit_rerun_if_env_changed() {
+        for var in ["MACA_PATH", "PATH"] {
+            println!("cargo:rerun-if-env-changed={var}");
+        }
+    }
+
+    /// Emits build script directives for the detected release and returns it.
+    ///
+    /// Declares a cfg flag for every release in `releases`,
+    /// and enables the flags of releases not newer than the installed one.
+    pub fn emit_version_cfg(&self, releases: &[MxVersion]) -> Option<MxVersion> {
+        if let Some(path) = self.version_file() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+
+        let version = self.version();
+        for release in releases {
+            let name = release.cfg_name();
+            println!("cargo:rustc-check-cfg=cfg({name})");
+            if version.is_some_and(|v| MxVersion::new(v.major, v.minor) >= *release) {
+                println!("cargo:rustc-cfg={name}");
+            }
+        }
+        version
+    }
+
+    fn version_file(&self) -> Option<PathBuf> {
+        VERSION_FILES
+            .iter()
+            .map(|name| self.home.join(name))
+            .find(|path| path.is_file())
+    }
+
+    fn header_version(&self) -> Option<MxVersion> {
+        let dir = read_dir(self.include.join("mcr")).ok()?;
+        dir.flatten()
+            .filter_map(|entry| read_to_string(entry.path()).ok())
+            .find_map(|text| parse_version_macros(&text))
+    }
+}
+
+/// Finds the first token that parses as a version, such as in `Version 2.20.2.6`.
+fn parse_version_text(text: &str) -> Option<MxVersion> {
+    text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find_map(|token| token.parse().ok())
+}
+
+fn parse_version_macros(text: &str) -> Option<MxVersion> {
+    let mut version = [None; 3];
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace();
+        if tokens.next() != Some("#define") {
+            continue;
+        }
+        let i = match tokens.next() {
+            Some("MACA_VERSION_MAJOR") => 0,
+            Some("MACA_VERSION_MINOR") => 1,
+            Some("MACA_VERSION_PATCH") => 2,
+            _ => continue,
+        };
+        version[i] = tokens.next().and_then(|n| n.parse().ok());
+    }
+    Some(MxVersion {
+        major: version[0]?,
+        minor: version[1]?,
+        patch: version[2].unwrap_or(0),
+    })
+}
+
+#[test]
+fn test_parse_version() {
+    assert_eq!("2.20".parse(), Ok(MxVersion::new(2, 20)));
+    assert_eq!(
+        parse_version_text("MACA Version 2.20.2.6\n"),
+        Some(MxVersion {
+            major: 2,
+            minor: 20,
+            patch: 2,
+        })
+    );
+    assert_eq!(parse_version_text("no version"), None);
+    assert_eq!(
+        parse_version_macros(
+            "#define MACA_VERSION_MAJOR 2\n#define MACA_VERSION_MINOR 16\n#define MACA_VERSION_PATCH 1\n"
+        ),
+        Some(MxVersion {
+            major: 2,
+            minor: 16,
+            patch: 1,
+        })
+    );
+    assert_eq!(MxVersion::new(2, 20).cfg_name(), "maca_2_20");
+    assert!(MxVersion::new(2, 9) < MxVersion::new(2, 20));
+}